@@ -0,0 +1,344 @@
+pub mod provider;
+pub mod token_store;
+
+pub use provider::{AuthProvider, PasswordProvider, PkceProvider};
+pub use token_store::{FileTokenStore, MemoryTokenStore, TokenStore};
+
+use crate::error::{PixivError, Result};
+use crate::network::{HttpClient, ReauthHandler};
+use chrono::{DateTime, Utc};
+use provider::{refresh_token_grant, DEFAULT_CLIENT_ID, DEFAULT_CLIENT_SECRET};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Duration as StdDuration;
+use tracing::{debug, info, warn};
+
+/// Pixiv API authentication client
+#[derive(Clone)]
+pub struct AuthClient {
+    /// HTTP client
+    client: HttpClient,
+    /// Authentication base URL
+    auth_url: String,
+    /// Optional pluggable persistence backend for the current token
+    token_store: Option<Arc<dyn TokenStore>>,
+    /// Optional pluggable login backend; defaults to the password grant via [`Self::login`]
+    provider: Option<Arc<dyn AuthProvider>>,
+    /// Most recently obtained token, tracked so [`ReauthHandler`] can tell
+    /// whether the token is close to expiring without a round trip
+    current: Arc<Mutex<Option<AuthResponse>>>,
+}
+
+impl std::fmt::Debug for AuthClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthClient")
+            .field("client", &self.client)
+            .field("auth_url", &self.auth_url)
+            .field("token_store", &self.token_store.is_some())
+            .finish()
+    }
+}
+
+/// Authentication response data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthResponse {
+    /// Access token
+    pub access_token: String,
+    /// Refresh token
+    pub refresh_token: String,
+    /// Token type
+    pub token_type: String,
+    /// Expiration time (seconds)
+    pub expires_in: u64,
+    /// User information
+    pub user: User,
+    /// Token acquisition time
+    #[serde(default)]
+    pub obtained_at: DateTime<Utc>,
+}
+
+/// User information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    /// User ID
+    pub id: u64,
+    /// Username
+    pub name: String,
+    /// Account name
+    pub account: String,
+    /// Email
+    pub email: Option<String>,
+    /// Avatar URL
+    pub profile_image_urls: ProfileImageUrls,
+}
+
+/// User avatar URL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileImageUrls {
+    /// Small avatar
+    pub px_16x16: Option<String>,
+    /// Medium avatar
+    pub px_50x50: Option<String>,
+    /// Large avatar
+    pub px_170x170: Option<String>,
+}
+
+impl AuthClient {
+    /// Create new authentication client
+    pub fn new() -> Result<Self> {
+        let client = HttpClient::new()?;
+        Ok(Self {
+            client,
+            auth_url: "https://oauth.secure.pixiv.net/auth/token".to_string(),
+            token_store: None,
+            provider: None,
+            current: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// Wrap this client as an [`Arc<dyn ReauthHandler>`], ready to hand to
+    /// [`HttpClient::with_reauth_handler`](crate::network::HttpClient::with_reauth_handler)
+    /// so an expired access token is transparently refreshed in-place.
+    pub fn into_reauth_handler(self) -> Arc<dyn ReauthHandler> {
+        Arc::new(self)
+    }
+
+    /// Attach a pluggable token store, used to persist tokens across restarts
+    pub fn with_token_store(mut self, store: Arc<dyn TokenStore>) -> Self {
+        self.token_store = Some(store);
+        self
+    }
+
+    /// Attach a pluggable [`AuthProvider`], used by [`Self::authenticate`] and
+    /// [`Self::refresh_with_provider`] instead of the hardcoded password grant
+    pub fn with_provider(mut self, provider: Arc<dyn AuthProvider>) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Log in via the [`AuthProvider`] attached with [`Self::with_provider`]
+    ///
+    /// Use this instead of [`Self::login`] for flows other than the
+    /// deprecated password grant, e.g. [`PkceProvider`].
+    pub async fn authenticate(&mut self) -> Result<AuthResponse> {
+        let provider = self
+            .provider
+            .clone()
+            .ok_or_else(|| PixivError::AuthError("No AuthProvider configured; call with_provider first".to_string()))?;
+
+        let auth_response = provider.authenticate(&mut self.client).await?;
+        let auth_response = self.apply_and_persist(auth_response).await?;
+        info!(user_id = %auth_response.user.id, "Login successful");
+        Ok(auth_response)
+    }
+
+    /// Refresh via the [`AuthProvider`] attached with [`Self::with_provider`]
+    pub async fn refresh_with_provider(&mut self, refresh_token: &str) -> Result<AuthResponse> {
+        let provider = self
+            .provider
+            .clone()
+            .ok_or_else(|| PixivError::AuthError("No AuthProvider configured; call with_provider first".to_string()))?;
+
+        let auth_response = provider.refresh(&mut self.client, refresh_token).await?;
+        let auth_response = self.apply_and_persist(auth_response).await?;
+        info!("Access token refreshed successfully");
+        Ok(auth_response)
+    }
+
+    /// Load a previously persisted token from the configured store, if any
+    ///
+    /// On success, the loaded access/refresh tokens are applied to the
+    /// underlying [`HttpClient`] so subsequent requests use them immediately.
+    pub async fn restore_from_store(&mut self) -> Result<Option<AuthResponse>> {
+        let Some(store) = self.token_store.clone() else {
+            return Ok(None);
+        };
+
+        let loaded = store.load().await?;
+        if let Some(auth_response) = &loaded {
+            self.client.set_access_token(auth_response.access_token.clone());
+            self.client.set_refresh_token(auth_response.refresh_token.clone());
+            *self.current.lock().unwrap() = Some(auth_response.clone());
+            debug!(user_id = %auth_response.user.id, "Restored token from store");
+        }
+
+        Ok(loaded)
+    }
+
+    /// Ensure the current token is valid, transparently refreshing it first if needed
+    ///
+    /// Checks [`Self::is_token_expired`] against the most recently obtained
+    /// token (falling back to the configured [`TokenStore`] if none has been
+    /// loaded yet this session), refreshes via [`Self::refresh_access_token`]
+    /// when it's stale, and returns the resulting token either way. Cheap
+    /// enough to call before every API request instead of manually threading
+    /// the refresh dance through call sites.
+    pub async fn ensure_valid_token(&mut self) -> Result<AuthResponse> {
+        let mut current = self.current.lock().unwrap().clone();
+        if current.is_none() {
+            current = self.restore_from_store().await?;
+        }
+
+        let Some(current) = current else {
+            return Err(PixivError::AuthError("No token available; call login() first".to_string()));
+        };
+
+        if !self.is_token_expired(&current) {
+            return Ok(current);
+        }
+
+        debug!("Stored token is expired, refreshing");
+        self.refresh_access_token(&current.refresh_token).await
+    }
+
+    /// Persist the given token through the configured store, if any
+    async fn persist(&self, auth_response: &AuthResponse) -> Result<()> {
+        if let Some(store) = &self.token_store {
+            store.save(auth_response).await?;
+        }
+        Ok(())
+    }
+
+    /// Apply a freshly obtained token to the underlying client, track it as
+    /// current, and persist it through the configured store, if any
+    async fn apply_and_persist(&mut self, auth_response: AuthResponse) -> Result<AuthResponse> {
+        self.client.set_access_token(auth_response.access_token.clone());
+        self.client.set_refresh_token(auth_response.refresh_token.clone());
+        *self.current.lock().unwrap() = Some(auth_response.clone());
+        self.persist(&auth_response).await?;
+        Ok(auth_response)
+    }
+
+    /// Login with username and password
+    ///
+    /// This is the deprecated password grant, which Pixiv no longer accepts
+    /// for most accounts; see [`PkceProvider`] via [`Self::with_provider`]
+    /// and [`Self::authenticate`] for the current login flow.
+    pub async fn login(&mut self, username: &str, password: &str) -> Result<AuthResponse> {
+        debug!(username = %username, "Attempting login");
+
+        let provider = PasswordProvider::new(username, password).with_auth_url(self.auth_url.clone());
+        let auth_response = provider.authenticate(&mut self.client).await.map_err(|e| {
+            warn!(error = %e, "Login failed");
+            e
+        })?;
+        let auth_response = self.apply_and_persist(auth_response).await?;
+
+        info!(user_id = %auth_response.user.id, "Login successful");
+        Ok(auth_response)
+    }
+
+    /// Get new access token using refresh token
+    pub async fn refresh_access_token(&mut self, refresh_token: &str) -> Result<AuthResponse> {
+        debug!("Refreshing access token");
+
+        let auth_response = refresh_token_grant(
+            &mut self.client,
+            &self.auth_url,
+            DEFAULT_CLIENT_ID,
+            DEFAULT_CLIENT_SECRET,
+            refresh_token,
+        )
+        .await
+        .map_err(|e| {
+            warn!(error = %e, "Token refresh failed");
+            e
+        })?;
+        let auth_response = self.apply_and_persist(auth_response).await?;
+
+        info!("Access token refreshed successfully");
+        Ok(auth_response)
+    }
+
+    /// Check if access token is expired
+    pub fn is_token_expired(&self, auth_response: &AuthResponse) -> bool {
+        let now = Utc::now();
+        let expires_at = auth_response.obtained_at + chrono::Duration::seconds(auth_response.expires_in as i64);
+        
+        // Consider token expired 5 minutes in advance
+        let buffer = chrono::Duration::minutes(5);
+        now + buffer > expires_at
+    }
+
+    /// Get mutable reference to HTTP client
+    pub fn client_mut(&mut self) -> &mut HttpClient {
+        &mut self.client
+    }
+
+    /// Get immutable reference to HTTP client
+    pub fn client(&self) -> &HttpClient {
+        &self.client
+    }
+}
+
+#[async_trait::async_trait]
+impl ReauthHandler for AuthClient {
+    async fn refresh(&self) -> Result<String> {
+        let refresh_token = self
+            .current
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|auth| auth.refresh_token.clone())
+            .or_else(|| self.client.refresh_token())
+            .ok_or_else(|| PixivError::AuthError("No refresh token available".to_string()))?;
+
+        let auth_response = self.clone().refresh_access_token(&refresh_token).await?;
+        Ok(auth_response.access_token)
+    }
+
+    fn should_refresh(&self, skew: StdDuration) -> bool {
+        let Some(current) = self.current.lock().unwrap().clone() else {
+            return false;
+        };
+        let Ok(skew) = chrono::Duration::from_std(skew) else {
+            return false;
+        };
+        let expires_at = current.obtained_at + chrono::Duration::seconds(current.expires_in as i64);
+        Utc::now() + skew >= expires_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auth_client_creation() {
+        let result = AuthClient::new();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_token_expiry_check() {
+        let mut auth_response = AuthResponse {
+            access_token: "test_token".to_string(),
+            refresh_token: "refresh_token".to_string(),
+            token_type: "Bearer".to_string(),
+            expires_in: 3600, // 1小时
+            user: User {
+                id: 12345,
+                name: "Test User".to_string(),
+                account: "testuser".to_string(),
+                email: None,
+                profile_image_urls: ProfileImageUrls {
+                    px_16x16: None,
+                    px_50x50: None,
+                    px_170x170: None,
+                },
+            },
+            obtained_at: Utc::now(),
+        };
+
+        let auth_client = AuthClient::new().unwrap();
+        
+        // 新令牌不应该过期
+        assert!(!auth_client.is_token_expired(&auth_response));
+        
+        // 设置令牌为过去时间
+        auth_response.obtained_at = Utc::now() - chrono::Duration::hours(2);
+        
+        // 过期令牌应该被检测到
+        assert!(auth_client.is_token_expired(&auth_response));
+    }
+}
\ No newline at end of file
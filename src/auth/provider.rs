@@ -0,0 +1,243 @@
+//! Pluggable authentication backends for [`AuthClient`](super::AuthClient)
+//!
+//! Pixiv's password grant (`grant_type=password`) no longer issues tokens
+//! for most accounts, so logging in increasingly means driving a browser
+//! through Pixiv's OAuth login page and pasting back an authorization code.
+//! [`AuthProvider`] abstracts over "how do we obtain/refresh a token" so
+//! [`AuthClient`](super::AuthClient) doesn't need to hardcode either flow:
+//! [`PasswordProvider`] wraps the original password grant, [`PkceProvider`]
+//! implements the PKCE authorization-code flow Pixiv's official apps use.
+
+use super::AuthResponse;
+use crate::error::{PixivError, Result};
+use crate::network::HttpClient;
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Pixiv's official Android app client ID, shared by every login flow
+pub(crate) const DEFAULT_CLIENT_ID: &str = "MOBrBDS8blbauoSck0ZfDbtuzpyT";
+/// Pixiv's official Android app client secret, shared by every login flow
+pub(crate) const DEFAULT_CLIENT_SECRET: &str = "lsACyCD94FhDUtGTXi3QzcFE2uU1hqtDaKeqrdwj";
+/// Token endpoint shared by every login flow
+pub(crate) const DEFAULT_AUTH_URL: &str = "https://oauth.secure.pixiv.net/auth/token";
+
+/// A pluggable backend that can obtain and refresh a Pixiv [`AuthResponse`]
+///
+/// [`AuthClient`](super::AuthClient) holds one of these behind an `Arc` (see
+/// [`AuthClient::with_provider`](super::AuthClient::with_provider)) instead
+/// of hardcoding a single login flow, so new flows can be added without
+/// touching [`AuthClient`](super::AuthClient) itself.
+#[async_trait]
+pub trait AuthProvider: Send + Sync {
+    /// Perform the initial login, returning a fresh token
+    async fn authenticate(&self, client: &mut HttpClient) -> Result<AuthResponse>;
+
+    /// Exchange `refresh_token` for a new access token
+    async fn refresh(&self, client: &mut HttpClient, refresh_token: &str) -> Result<AuthResponse>;
+}
+
+/// The deprecated username/password grant, wrapping [`AuthClient`](super::AuthClient)'s original behavior
+pub struct PasswordProvider {
+    username: String,
+    password: String,
+    auth_url: String,
+    client_id: String,
+    client_secret: String,
+}
+
+impl PasswordProvider {
+    /// Create a provider that logs in with a Pixiv username and password
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+            auth_url: DEFAULT_AUTH_URL.to_string(),
+            client_id: DEFAULT_CLIENT_ID.to_string(),
+            client_secret: DEFAULT_CLIENT_SECRET.to_string(),
+        }
+    }
+
+    /// Override the token endpoint
+    pub fn with_auth_url(mut self, auth_url: impl Into<String>) -> Self {
+        self.auth_url = auth_url.into();
+        self
+    }
+}
+
+#[async_trait]
+impl AuthProvider for PasswordProvider {
+    async fn authenticate(&self, client: &mut HttpClient) -> Result<AuthResponse> {
+        let mut form = HashMap::new();
+        form.insert("client_id", self.client_id.as_str());
+        form.insert("client_secret", self.client_secret.as_str());
+        form.insert("grant_type", "password");
+        form.insert("username", self.username.as_str());
+        form.insert("password", self.password.as_str());
+        form.insert("get_secure_url", "true");
+        post_token_request(client, &self.auth_url, &form).await
+    }
+
+    async fn refresh(&self, client: &mut HttpClient, refresh_token: &str) -> Result<AuthResponse> {
+        refresh_token_grant(client, &self.auth_url, &self.client_id, &self.client_secret, refresh_token).await
+    }
+}
+
+/// Pixiv's current OAuth login flow: PKCE authorization code, no password required
+///
+/// Usage is two-step, since completing login requires a browser: call
+/// [`Self::generate`] and open [`Self::login_url`], then once the user
+/// finishes logging in and pastes back the `code` Pixiv redirects them
+/// with, call [`Self::with_code`] before passing the provider to
+/// [`AuthClient::authenticate`](super::AuthClient::authenticate).
+pub struct PkceProvider {
+    code_verifier: String,
+    code: Option<String>,
+    auth_url: String,
+    authorize_url: String,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+}
+
+impl PkceProvider {
+    /// Generate a fresh PKCE `code_verifier`, ready to build a login URL from
+    pub fn generate() -> Self {
+        Self {
+            code_verifier: generate_code_verifier(),
+            code: None,
+            auth_url: DEFAULT_AUTH_URL.to_string(),
+            authorize_url: "https://app-api.pixiv.net/web/v1/login".to_string(),
+            client_id: DEFAULT_CLIENT_ID.to_string(),
+            client_secret: DEFAULT_CLIENT_SECRET.to_string(),
+            redirect_uri: "https://app-api.pixiv.net/web/v1/users/auth/pixiv/callback".to_string(),
+        }
+    }
+
+    /// Attach the authorization `code` pasted back after the user completes browser login
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// The URL to open in a browser to complete login
+    ///
+    /// After the user signs in, Pixiv redirects to `redirect_uri` with a
+    /// `code` query parameter; that value is what [`Self::with_code`] wants.
+    pub fn login_url(&self) -> String {
+        format!(
+            "{}?code_challenge={}&code_challenge_method=S256&client=pixiv-android",
+            self.authorize_url,
+            code_challenge(&self.code_verifier)
+        )
+    }
+}
+
+#[async_trait]
+impl AuthProvider for PkceProvider {
+    async fn authenticate(&self, client: &mut HttpClient) -> Result<AuthResponse> {
+        let code = self
+            .code
+            .as_ref()
+            .ok_or_else(|| PixivError::AuthError("PkceProvider::authenticate called before with_code".to_string()))?;
+
+        let mut form = HashMap::new();
+        form.insert("client_id", self.client_id.as_str());
+        form.insert("client_secret", self.client_secret.as_str());
+        form.insert("grant_type", "authorization_code");
+        form.insert("code", code.as_str());
+        form.insert("code_verifier", self.code_verifier.as_str());
+        form.insert("redirect_uri", self.redirect_uri.as_str());
+        post_token_request(client, &self.auth_url, &form).await
+    }
+
+    async fn refresh(&self, client: &mut HttpClient, refresh_token: &str) -> Result<AuthResponse> {
+        refresh_token_grant(client, &self.auth_url, &self.client_id, &self.client_secret, refresh_token).await
+    }
+}
+
+/// A random 43-128 character `code_verifier` drawn from the PKCE unreserved character set
+fn generate_code_verifier() -> String {
+    const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-._~";
+    let mut rng = rand::thread_rng();
+    let len = rng.gen_range(43..=128);
+    (0..len).map(|_| CHARSET[rng.gen_range(0..CHARSET.len())] as char).collect()
+}
+
+/// `code_challenge = base64url_nopad(sha256(code_verifier))`
+fn code_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+/// POST a token-endpoint request with the client's security headers attached, and parse the response
+pub(crate) async fn post_token_request(
+    client: &mut HttpClient,
+    auth_url: &str,
+    form: &HashMap<&str, &str>,
+) -> Result<AuthResponse> {
+    let security_headers = client.generate_security_headers();
+    let mut request = client.client.post(auth_url);
+    for (key, value) in security_headers {
+        request = request.header(&key, value);
+    }
+    request = request.form(form);
+
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Failed to get error information".to_string());
+        return Err(PixivError::AuthError(format!("Login failed: {}", error_text)));
+    }
+
+    let mut auth_response: AuthResponse = response.json().await?;
+    auth_response.obtained_at = chrono::Utc::now();
+    Ok(auth_response)
+}
+
+/// The `grant_type=refresh_token` request shared by every [`AuthProvider`]
+pub(crate) async fn refresh_token_grant(
+    client: &mut HttpClient,
+    auth_url: &str,
+    client_id: &str,
+    client_secret: &str,
+    refresh_token: &str,
+) -> Result<AuthResponse> {
+    let mut form = HashMap::new();
+    form.insert("client_id", client_id);
+    form.insert("client_secret", client_secret);
+    form.insert("grant_type", "refresh_token");
+    form.insert("refresh_token", refresh_token);
+    form.insert("get_secure_url", "true");
+    post_token_request(client, auth_url, &form).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_verifier_length_within_pkce_bounds() {
+        for _ in 0..20 {
+            let verifier = generate_code_verifier();
+            assert!(verifier.len() >= 43 && verifier.len() <= 128);
+            assert!(verifier.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == '_' || c == '~'));
+        }
+    }
+
+    #[test]
+    fn test_code_challenge_is_url_safe_no_pad_base64() {
+        // Known SHA-256/base64url test vector for the literal string "test"
+        let challenge = code_challenge("test");
+        assert_eq!(challenge, "n4bQgYhMfWWaL-qgxVrQFaO_TxsrC4Is0V1sFbDwCgg");
+    }
+
+    #[test]
+    fn test_login_url_includes_pkce_params() {
+        let provider = PkceProvider::generate();
+        let url = provider.login_url();
+        assert!(url.contains("code_challenge="));
+        assert!(url.contains("code_challenge_method=S256"));
+        assert!(url.contains("client=pixiv-android"));
+    }
+}
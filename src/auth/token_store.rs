@@ -0,0 +1,167 @@
+use crate::auth::AuthResponse;
+use crate::error::{PixivError, Result};
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+/// Pluggable persistence backend for [`AuthResponse`]
+///
+/// Lets downstream apps plug in keyring/secret-service backends or encrypted
+/// stores without touching this crate, the way Proxmox generalized user auth
+/// behind an `ApiAuth` trait.
+#[async_trait]
+pub trait TokenStore: std::fmt::Debug + Send + Sync {
+    /// Load the previously saved token, if any
+    async fn load(&self) -> Result<Option<AuthResponse>>;
+
+    /// Persist a token, overwriting whatever was previously saved
+    async fn save(&self, auth: &AuthResponse) -> Result<()>;
+
+    /// Remove any saved token
+    async fn clear(&self) -> Result<()>;
+}
+
+/// `TokenStore` that persists the token as JSON on disk
+#[derive(Debug, Clone)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl FileTokenStore {
+    /// Create a store backed by the given file path
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl TokenStore for FileTokenStore {
+    async fn load(&self) -> Result<Option<AuthResponse>> {
+        match tokio::fs::read(&self.path).await {
+            Ok(bytes) => {
+                debug!(path = ?self.path, "Loading token from file store");
+                let auth: AuthResponse = serde_json::from_slice(&bytes)?;
+                Ok(Some(auth))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(PixivError::Unknown(format!(
+                "Failed to read token file {}: {}",
+                self.path.display(),
+                e
+            ))),
+        }
+    }
+
+    async fn save(&self, auth: &AuthResponse) -> Result<()> {
+        debug!(path = ?self.path, "Saving token to file store");
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                PixivError::Unknown(format!("Failed to create directory {}: {}", parent.display(), e))
+            })?;
+        }
+        let data = serde_json::to_vec_pretty(auth)?;
+        tokio::fs::write(&self.path, data).await.map_err(|e| {
+            PixivError::Unknown(format!("Failed to write token file {}: {}", self.path.display(), e))
+        })
+    }
+
+    async fn clear(&self) -> Result<()> {
+        match tokio::fs::remove_file(&self.path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(PixivError::Unknown(format!(
+                "Failed to remove token file {}: {}",
+                self.path.display(),
+                e
+            ))),
+        }
+    }
+}
+
+/// `TokenStore` that keeps the token in memory only, for tests or ephemeral sessions
+#[derive(Debug, Clone, Default)]
+pub struct MemoryTokenStore {
+    state: Arc<Mutex<Option<AuthResponse>>>,
+}
+
+impl MemoryTokenStore {
+    /// Create an empty in-memory store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TokenStore for MemoryTokenStore {
+    async fn load(&self) -> Result<Option<AuthResponse>> {
+        Ok(self.state.lock().unwrap().clone())
+    }
+
+    async fn save(&self, auth: &AuthResponse) -> Result<()> {
+        *self.state.lock().unwrap() = Some(auth.clone());
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<()> {
+        *self.state.lock().unwrap() = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{ProfileImageUrls, User};
+    use chrono::Utc;
+
+    fn sample_auth() -> AuthResponse {
+        AuthResponse {
+            access_token: "access".to_string(),
+            refresh_token: "refresh".to_string(),
+            token_type: "Bearer".to_string(),
+            expires_in: 3600,
+            user: User {
+                id: 1,
+                name: "test".to_string(),
+                account: "test".to_string(),
+                email: None,
+                profile_image_urls: ProfileImageUrls {
+                    px_16x16: None,
+                    px_50x50: None,
+                    px_170x170: None,
+                },
+            },
+            obtained_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_roundtrip() {
+        let store = MemoryTokenStore::new();
+        assert!(store.load().await.unwrap().is_none());
+
+        store.save(&sample_auth()).await.unwrap();
+        let loaded = store.load().await.unwrap().unwrap();
+        assert_eq!(loaded.access_token, "access");
+
+        store.clear().await.unwrap();
+        assert!(store.load().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_file_store_roundtrip() {
+        let mut path = std::env::temp_dir();
+        path.push(format!("pixiv_rs_token_store_test_{}.json", std::process::id()));
+        let store = FileTokenStore::new(&path);
+
+        assert!(store.load().await.unwrap().is_none());
+
+        store.save(&sample_auth()).await.unwrap();
+        let loaded = store.load().await.unwrap().unwrap();
+        assert_eq!(loaded.refresh_token, "refresh");
+
+        store.clear().await.unwrap();
+        assert!(store.load().await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,58 @@
+//! Bounded-concurrency batch fetching over a list of inputs
+//!
+//! A fixed-size worker pool: at most `concurrency` requests are in flight at
+//! once, built on [`futures::stream::StreamExt::buffer_unordered`]/
+//! [`buffered`](futures::stream::StreamExt::buffered). The per-item future is
+//! supplied by the caller, so the same pool shape works for any fan-out
+//! (illustration details, per-user illusts, per-illust comments, ...)
+//! without duplicating the concurrency-limiting logic at each call site.
+
+use crate::error::Result;
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use std::future::Future;
+
+/// Run `f` over `items` with at most `concurrency` calls in flight at once
+///
+/// Results are yielded in completion order, not input order; use
+/// [`collect_ordered`] when the original order must be preserved.
+pub fn fetch_many<I, F, Fut, T>(items: I, concurrency: usize, f: F) -> impl Stream<Item = Result<T>>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    stream::iter(items).map(f).buffer_unordered(concurrency.max(1))
+}
+
+/// Run `f` over `items` with at most `concurrency` calls in flight at once,
+/// collecting the results in input order
+///
+/// Fails fast, returning the first error encountered while draining the pool.
+pub async fn collect_ordered<I, F, Fut, T>(items: I, concurrency: usize, f: F) -> Result<Vec<T>>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    stream::iter(items).map(f).buffered(concurrency.max(1)).try_collect().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future;
+
+    #[tokio::test]
+    async fn test_fetch_many_yields_every_item() {
+        let results: Vec<_> = fetch_many(0..5, 2, |n| future::ready(Ok(n * 2))).collect().await;
+        let mut values: Vec<i32> = results.into_iter().collect::<Result<Vec<_>>>().unwrap();
+        values.sort_unstable();
+        assert_eq!(values, vec![0, 2, 4, 6, 8]);
+    }
+
+    #[tokio::test]
+    async fn test_collect_ordered_preserves_input_order() {
+        let values = collect_ordered(0..5, 2, |n| future::ready(Ok(n * 2))).await.unwrap();
+        assert_eq!(values, vec![0, 2, 4, 6, 8]);
+    }
+}
@@ -1,13 +1,15 @@
 use crate::error::{PixivError, Result};
 use crate::models::app::{
-    CommentsResponse, ContentType, Duration, Filter, FollowRestrict, IllustBookmarkResponse,
+    Comment, CommentsResponse, ContentType, Duration, Filter, FollowRestrict, Illust, IllustBookmarkResponse,
     IllustDetail, IllustFollowResponse, IllustRelatedResponse, RankingMode, RankingResponse, RecommendedResponse,
     SearchIllustResponse, SearchTarget, Sort, TrendingTagsResponse, UgoiraMetadataResponse,
     UserFollowingResponse, UserFollowerResponse, UserIllustrationsResponse, UserMypixivResponse,
-    UserNovelsResponse, NovelSeriesResponse, NovelNewResponse, NovelFollowResponse, NovelRecommendedResponse, SearchNovelResponse, UserBookmarksNovelResponse, WebviewNovelResponse, NovelSearchTarget, NovelFollowRestrict, Novel,
+    UserNovelsResponse, NovelSeriesResponse, NovelNewResponse, NovelFollowResponse, NovelRecommendedResponse, SearchNovelResponse, UserBookmarksNovelResponse, WebviewNovelResponse, NovelSearchTarget, NovelFollowRestrict, Novel, UserPreview,
 };
 use crate::network::HttpClient;
-use regex::Regex;
+use crate::pagination::Pager;
+use crate::utils::join_base_url;
+use futures::stream::Stream;
 use std::collections::HashMap;
 use tracing::debug;
 
@@ -29,6 +31,12 @@ impl AppClient {
         }
     }
 
+    /// The underlying [`HttpClient`], for callers that need lower-level access
+    /// (e.g. downloading a ugoira ZIP directly)
+    pub fn http_client(&self) -> &HttpClient {
+        &self.http_client
+    }
+
     /// Set API base URL
     pub fn set_base_url(&mut self, url: String) {
         self.base_url = url;
@@ -55,7 +63,7 @@ impl AppClient {
     pub async fn illust_detail(&self, illust_id: u64) -> Result<IllustDetail> {
         debug!(illust_id = %illust_id, "Fetching illustration detail");
         
-        let url = format!("{}/v1/illust/detail", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/illust/detail");
         let params = [("illust_id", illust_id.to_string())];
         
         let response = self
@@ -69,6 +77,21 @@ impl AppClient {
         Ok(detail)
     }
 
+    /// Fetch details for every ID in `ids`, running up to `concurrency` requests at once
+    ///
+    /// A worker-pool fan-out over [`Self::illust_detail`]: results stream back
+    /// as each request completes rather than in input order. Use
+    /// [`Self::illust_details_batch_ordered`] when the original order matters.
+    pub fn illust_details_batch(&self, ids: Vec<u64>, concurrency: usize) -> impl Stream<Item = Result<IllustDetail>> + '_ {
+        crate::batch::fetch_many(ids, concurrency, move |id| self.illust_detail(id))
+    }
+
+    /// Fetch details for every ID in `ids`, running up to `concurrency` requests at once,
+    /// collecting the results in the same order as `ids`
+    pub async fn illust_details_batch_ordered(&self, ids: Vec<u64>, concurrency: usize) -> Result<Vec<IllustDetail>> {
+        crate::batch::collect_ordered(ids, concurrency, move |id| self.illust_detail(id)).await
+    }
+
     /// Get illustration ranking
     ///
     /// # Arguments
@@ -105,7 +128,7 @@ impl AppClient {
             "Fetching illustration ranking"
         );
         
-        let url = format!("{}/v1/illust/ranking", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/illust/ranking");
         let mut params = Vec::new();
         params.push(("mode", mode.to_string()));
         params.push(("filter", filter.to_string()));
@@ -125,10 +148,25 @@ impl AppClient {
         
         let text = response.text().await?;
         let ranking: RankingResponse = serde_json::from_str(&text)?;
-        
+
         Ok(ranking)
     }
 
+    /// Stream illustration ranking entries, auto-paginating across `next_url` pages
+    pub fn illust_ranking_pager(
+        &self,
+        mode: RankingMode,
+        filter: Filter,
+        date: Option<&str>,
+    ) -> Pager<RankingResponse, Illust> {
+        let mut params = vec![("mode", mode.to_string()), ("filter", filter.to_string())];
+        if let Some(date) = date {
+            params.push(("date", date.to_string()));
+        }
+        let url = format!("{}?{}", join_base_url(&self.base_url, "/v1/illust/ranking"), build_query(&params));
+        Pager::new(self.http_client.clone(), url)
+    }
+
     /// Get recommended illustrations
     ///
     /// # Arguments
@@ -187,7 +225,7 @@ impl AppClient {
             "Fetching recommended illustrations"
         );
         
-        let url = format!("{}/v1/illust/recommended", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/illust/recommended");
         let mut params = Vec::new();
         params.push(("content_type".to_string(), content_type.to_string()));
         params.push(("include_ranking_label".to_string(), include_ranking_label.to_string()));
@@ -296,7 +334,7 @@ impl AppClient {
             "Searching illustrations"
         );
         
-        let url = format!("{}/v1/search/illust", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/search/illust");
         let mut params = Vec::new();
         params.push(("word", word.to_string()));
         params.push(("search_target", search_target.to_string()));
@@ -330,10 +368,82 @@ impl AppClient {
         
         let text = response.text().await?;
         let search_result: SearchIllustResponse = serde_json::from_str(&text)?;
-        
+
         Ok(search_result)
     }
 
+    /// Stream search results, auto-paginating across `next_url` pages
+    ///
+    /// Takes the same parameters as [`Self::search_illust`]; see its docs for semantics.
+    #[allow(clippy::too_many_arguments)]
+    pub fn search_illust_pager(
+        &self,
+        word: &str,
+        search_target: SearchTarget,
+        sort: Sort,
+        duration: Option<Duration>,
+        start_date: Option<&str>,
+        end_date: Option<&str>,
+        filter: Filter,
+        search_ai_type: Option<u32>,
+    ) -> Pager<SearchIllustResponse, Illust> {
+        let mut params = vec![
+            ("word", word.to_string()),
+            ("search_target", search_target.to_string()),
+            ("sort", sort.to_string()),
+            ("filter", filter.to_string()),
+        ];
+        if let Some(duration) = duration {
+            params.push(("duration", duration.to_string()));
+        }
+        if let Some(start_date) = start_date {
+            params.push(("start_date", start_date.to_string()));
+        }
+        if let Some(end_date) = end_date {
+            params.push(("end_date", end_date.to_string()));
+        }
+        if let Some(search_ai_type) = search_ai_type {
+            params.push(("search_ai_type", search_ai_type.to_string()));
+        }
+
+        let url = format!("{}?{}", join_base_url(&self.base_url, "/v1/search/illust"), build_query(&params));
+        Pager::new(self.http_client.clone(), url)
+    }
+
+    /// Search illustrations using the [advanced query DSL](crate::query), auto-paginating
+    /// and filtering client-side against the full parsed expression
+    ///
+    /// The broadest positive terms in `expr` are pushed into Pixiv's native
+    /// `word` param to narrow what gets fetched; every page is then filtered
+    /// against the full expression (tags, `OR`, negation, and numeric
+    /// predicates aren't faithfully expressible in Pixiv's own search) before
+    /// being yielded. A malformed `expr` surfaces as a single
+    /// [`PixivError::QueryParseError`] item rather than an `Err` return, so
+    /// the method can still satisfy the `impl Stream` signature.
+    pub fn search_illust_query(
+        &self,
+        expr: &str,
+        sort: Sort,
+        filter: Filter,
+    ) -> std::pin::Pin<Box<dyn futures::stream::Stream<Item = Result<Illust>> + Send + '_>> {
+        use futures::stream::StreamExt;
+
+        let ast = match crate::query::parse(expr) {
+            Ok(ast) => ast,
+            Err(e) => return Box::pin(futures::stream::once(async move { Err(PixivError::from(e)) })),
+        };
+
+        let pager = self.search_illust_pager(&ast.word_hint(), SearchTarget::PartialMatchForTags, sort, None, None, None, filter, None);
+
+        Box::pin(pager.filter(move |item| {
+            let keep = match item {
+                Ok(illust) => ast.matches(illust),
+                Err(_) => true,
+            };
+            futures::future::ready(keep)
+        }))
+    }
+
     /// Get illustrations from followed users
     ///
     /// # Arguments
@@ -362,7 +472,7 @@ impl AppClient {
             "Fetching follow illustrations"
         );
         
-        let url = format!("{}/v2/illust/follow", self.base_url);
+        let url = join_base_url(&self.base_url, "/v2/illust/follow");
         let mut params = Vec::new();
         params.push(("restrict", restrict.to_string()));
         
@@ -377,10 +487,26 @@ impl AppClient {
         
         let text = response.text().await?;
         let follow_response: IllustFollowResponse = serde_json::from_str(&text)?;
-        
+
         Ok(follow_response)
     }
 
+    /// Stream follow illustrations, auto-paginating across `next_url` pages
+    ///
+    /// # Example
+    /// ```rust
+    /// use futures::StreamExt;
+    ///
+    /// let mut illusts = client.illust_follow_pager(FollowRestrict::Public);
+    /// while let Some(illust) = illusts.next().await {
+    ///     let illust = illust?;
+    /// }
+    /// ```
+    pub fn illust_follow_pager(&self, restrict: FollowRestrict) -> Pager<IllustFollowResponse, Illust> {
+        let url = format!("{}?restrict={}", join_base_url(&self.base_url, "/v2/illust/follow"), restrict.to_string());
+        Pager::new(self.http_client.clone(), url)
+    }
+
     /// Get illustration comments
     ///
     /// # Arguments
@@ -413,7 +539,7 @@ impl AppClient {
             "Fetching illustration comments"
         );
         
-        let url = format!("{}/v1/illust/comments", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/illust/comments");
         let mut params = Vec::new();
         params.push(("illust_id", illust_id.to_string()));
         
@@ -432,10 +558,20 @@ impl AppClient {
         
         let text = response.text().await?;
         let comments: CommentsResponse = serde_json::from_str(&text)?;
-        
+
         Ok(comments)
     }
 
+    /// Fetch the first comment page for every ID in `illust_ids`, running up
+    /// to `concurrency` requests at once
+    pub fn illust_comments_batch(
+        &self,
+        illust_ids: Vec<u64>,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<CommentsResponse>> + '_ {
+        crate::batch::fetch_many(illust_ids, concurrency, move |id| self.illust_comments(id, None, None))
+    }
+
     /// Get related illustrations
     ///
     /// # Arguments
@@ -476,7 +612,7 @@ impl AppClient {
             "Fetching related illustrations"
         );
 
-        let url = format!("{}/v2/illust/related", self.base_url);
+        let url = join_base_url(&self.base_url, "/v2/illust/related");
         let mut params = Vec::new();
         params.push(("illust_id".to_string(), illust_id.to_string()));
         params.push(("filter".to_string(), filter.to_string()));
@@ -512,6 +648,13 @@ impl AppClient {
         Ok(related)
     }
 
+    /// Stream illustrations related to `illust_id`, auto-paginating across `next_url` pages
+    pub fn illust_related_pager(&self, illust_id: u64, filter: Filter) -> Pager<IllustRelatedResponse, Illust> {
+        let params = vec![("illust_id", illust_id.to_string()), ("filter", filter.to_string())];
+        let url = format!("{}?{}", join_base_url(&self.base_url, "/v2/illust/related"), build_query(&params));
+        Pager::new(self.http_client.clone(), url)
+    }
+
     /// Get user following list
     ///
     /// # Arguments
@@ -544,7 +687,7 @@ impl AppClient {
             "Fetching user following"
         );
         
-        let url = format!("{}/v1/user/following", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/user/following");
         let mut params = Vec::new();
         params.push(("user_id", user_id.to_string()));
         params.push(("restrict", restrict.to_string()));
@@ -560,10 +703,17 @@ impl AppClient {
         
         let text = response.text().await?;
         let following: UserFollowingResponse = serde_json::from_str(&text)?;
-        
+
         Ok(following)
     }
 
+    /// Stream the users `user_id` follows, auto-paginating across `next_url` pages
+    pub fn user_following_pager(&self, user_id: u64, restrict: FollowRestrict) -> Pager<UserFollowingResponse, UserPreview> {
+        let params = vec![("user_id", user_id.to_string()), ("restrict", restrict.to_string())];
+        let url = format!("{}?{}", join_base_url(&self.base_url, "/v1/user/following"), build_query(&params));
+        Pager::new(self.http_client.clone(), url)
+    }
+
     /// Get user followers list
     ///
     /// # Arguments
@@ -596,7 +746,7 @@ impl AppClient {
             "Fetching user followers"
         );
         
-        let url = format!("{}/v1/user/follower", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/user/follower");
         let mut params = Vec::new();
         params.push(("user_id", user_id.to_string()));
         params.push(("filter", filter.to_string()));
@@ -652,7 +802,7 @@ impl AppClient {
             "Fetching user illustrations"
         );
 
-        let url = format!("{}/v1/user/illusts", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/user/illusts");
         let mut params = Vec::new();
         params.push(("user_id", user_id.to_string()));
         params.push(("filter", filter.to_string()));
@@ -676,6 +826,32 @@ impl AppClient {
         Ok(illusts)
     }
 
+    /// Stream a user's illustrations, auto-paginating across `next_url` pages
+    pub fn user_illusts_pager(
+        &self,
+        user_id: u64,
+        content_type: Option<ContentType>,
+        filter: Filter,
+    ) -> Pager<UserIllustrationsResponse, Illust> {
+        let mut params = vec![("user_id", user_id.to_string()), ("filter", filter.to_string())];
+        if let Some(content_type) = content_type {
+            params.push(("type", content_type.to_string()));
+        }
+        let url = format!("{}?{}", join_base_url(&self.base_url, "/v1/user/illusts"), build_query(&params));
+        Pager::new(self.http_client.clone(), url)
+    }
+
+    /// Fetch the first illustrations page for every ID in `user_ids`, running
+    /// up to `concurrency` requests at once
+    pub fn user_illusts_batch(
+        &self,
+        user_ids: Vec<u64>,
+        filter: Filter,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<UserIllustrationsResponse>> + '_ {
+        crate::batch::fetch_many(user_ids, concurrency, move |id| self.user_illusts(id, None, filter.clone(), None))
+    }
+
     /// Get user mypixiv list
     ///
     /// # Arguments
@@ -704,7 +880,7 @@ impl AppClient {
             "Fetching user mypixiv"
         );
         
-        let url = format!("{}/v1/user/mypixiv", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/user/mypixiv");
         let mut params = Vec::new();
         params.push(("user_id", user_id.to_string()));
         
@@ -755,7 +931,7 @@ impl AppClient {
             "Adding illustration bookmark"
         );
         
-        let url = format!("{}/v2/illust/bookmark/add", self.base_url);
+        let url = join_base_url(&self.base_url, "/v2/illust/bookmark/add");
         let mut data = HashMap::new();
         data.insert("illust_id", illust_id.to_string());
         data.insert("restrict", restrict.to_string());
@@ -797,7 +973,7 @@ impl AppClient {
             "Deleting illustration bookmark"
         );
         
-        let url = format!("{}/v1/illust/bookmark/delete", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/illust/bookmark/delete");
         let mut data = HashMap::new();
         data.insert("illust_id", illust_id.to_string());
         
@@ -834,7 +1010,7 @@ impl AppClient {
             "Fetching trending tags"
         );
         
-        let url = format!("{}/v1/trending-tags/illust", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/trending-tags/illust");
         let params = [("filter", filter.to_string())];
         
         let response = self
@@ -870,7 +1046,7 @@ impl AppClient {
             "Fetching ugoira metadata"
         );
         
-        let url = format!("{}/v1/ugoira/metadata", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/ugoira/metadata");
         let params = [("illust_id", illust_id.to_string())];
         
         let response = self
@@ -916,7 +1092,7 @@ impl AppClient {
             "Fetching user novels"
         );
 
-        let url = format!("{}/v1/user/novels", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/user/novels");
         let mut params = Vec::new();
         params.push(("user_id", user_id.to_string()));
         params.push(("filter", filter.to_string()));
@@ -936,6 +1112,17 @@ impl AppClient {
         Ok(novels)
     }
 
+    /// Auto-paginating [`user_novels`](Self::user_novels), yielding one [`Novel`] at a time
+    pub fn user_novels_pager(&self, user_id: u64, filter: Filter, offset: Option<u32>) -> Pager<UserNovelsResponse, Novel> {
+        let mut params = vec![("user_id", user_id.to_string()), ("filter", filter.to_string())];
+        if let Some(offset) = offset {
+            params.push(("offset", offset.to_string()));
+        }
+
+        let url = format!("{}?{}", join_base_url(&self.base_url, "/v1/user/novels"), build_query(&params));
+        Pager::new(self.http_client.clone(), url)
+    }
+
     /// Get novel series
     ///
     /// # Arguments
@@ -968,7 +1155,7 @@ impl AppClient {
             "Fetching novel series"
         );
 
-        let url = format!("{}/v2/novel/series", self.base_url);
+        let url = join_base_url(&self.base_url, "/v2/novel/series");
         let mut params = Vec::new();
         params.push(("series_id", series_id.to_string()));
         params.push(("filter", filter.to_string()));
@@ -1007,7 +1194,7 @@ impl AppClient {
     ) -> Result<Novel> {
         debug!(novel_id = %novel_id, "Fetching novel detail");
 
-        let url = format!("{}/v2/novel/detail", self.base_url);
+        let url = join_base_url(&self.base_url, "/v2/novel/detail");
         let params = [("novel_id", novel_id.to_string())];
 
         let response = self
@@ -1050,7 +1237,7 @@ impl AppClient {
             "Fetching new novels"
         );
 
-        let url = format!("{}/v1/novel/new", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/novel/new");
         let mut params = Vec::new();
         params.push(("filter", filter.to_string()));
 
@@ -1097,7 +1284,7 @@ impl AppClient {
             "Fetching follow novels"
         );
 
-        let url = format!("{}/v1/novel/follow", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/novel/follow");
         let mut params = Vec::new();
         params.push(("restrict", restrict.to_string()));
 
@@ -1116,6 +1303,17 @@ impl AppClient {
         Ok(follow_response)
     }
 
+    /// Auto-paginating [`novel_follow`](Self::novel_follow), yielding one [`Novel`] at a time
+    pub fn novel_follow_pager(&self, restrict: NovelFollowRestrict, offset: Option<u32>) -> Pager<NovelFollowResponse, Novel> {
+        let mut params = vec![("restrict", restrict.to_string())];
+        if let Some(offset) = offset {
+            params.push(("offset", offset.to_string()));
+        }
+
+        let url = format!("{}?{}", join_base_url(&self.base_url, "/v1/novel/follow"), build_query(&params));
+        Pager::new(self.http_client.clone(), url)
+    }
+
     /// Get novel comments
     ///
     /// # Arguments
@@ -1148,7 +1346,7 @@ impl AppClient {
             "Fetching novel comments"
         );
 
-        let url = format!("{}/v1/novel/comments", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/novel/comments");
         let mut params = Vec::new();
         params.push(("novel_id", novel_id.to_string()));
 
@@ -1171,6 +1369,25 @@ impl AppClient {
         Ok(comments)
     }
 
+    /// Auto-paginating [`novel_comments`](Self::novel_comments), yielding one [`Comment`] at a time
+    pub fn novel_comments_pager(
+        &self,
+        novel_id: u64,
+        offset: Option<u32>,
+        include_total_comments: Option<bool>,
+    ) -> Pager<CommentsResponse, Comment> {
+        let mut params = vec![("novel_id", novel_id.to_string())];
+        if let Some(offset) = offset {
+            params.push(("offset", offset.to_string()));
+        }
+        if let Some(include_total_comments) = include_total_comments {
+            params.push(("include_total_comments", include_total_comments.to_string()));
+        }
+
+        let url = format!("{}?{}", join_base_url(&self.base_url, "/v1/novel/comments"), build_query(&params));
+        Pager::new(self.http_client.clone(), url)
+    }
+
     /// Get recommended novels
     ///
     /// # Arguments
@@ -1219,7 +1436,7 @@ impl AppClient {
             "Fetching recommended novels"
         );
 
-        let url = format!("{}/v1/novel/recommended", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/novel/recommended");
         let mut params = Vec::new();
         params.push(("include_ranking_label".to_string(), include_ranking_label.to_string()));
         params.push(("filter".to_string(), filter.to_string()));
@@ -1320,7 +1537,7 @@ impl AppClient {
             "Searching novels"
         );
 
-        let url = format!("{}/v1/search/novel", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/search/novel");
         let mut params = Vec::new();
         params.push(("word", word.to_string()));
         params.push(("search_target", search_target.to_string()));
@@ -1356,6 +1573,46 @@ impl AppClient {
         Ok(search_result)
     }
 
+    /// Auto-paginating [`search_novel`](Self::search_novel), yielding one [`Novel`] at a time
+    #[allow(clippy::too_many_arguments)]
+    pub fn search_novel_pager(
+        &self,
+        word: &str,
+        search_target: NovelSearchTarget,
+        sort: Sort,
+        merge_plain_keyword_results: bool,
+        include_translated_tag_results: bool,
+        start_date: Option<&str>,
+        end_date: Option<&str>,
+        filter: Filter,
+        search_ai_type: Option<u32>,
+        offset: Option<u32>,
+    ) -> Pager<SearchNovelResponse, Novel> {
+        let mut params = vec![
+            ("word", word.to_string()),
+            ("search_target", search_target.to_string()),
+            ("sort", sort.to_string()),
+            ("merge_plain_keyword_results", merge_plain_keyword_results.to_string()),
+            ("include_translated_tag_results", include_translated_tag_results.to_string()),
+            ("filter", filter.to_string()),
+        ];
+        if let Some(start_date) = start_date {
+            params.push(("start_date", start_date.to_string()));
+        }
+        if let Some(end_date) = end_date {
+            params.push(("end_date", end_date.to_string()));
+        }
+        if let Some(search_ai_type) = search_ai_type {
+            params.push(("search_ai_type", search_ai_type.to_string()));
+        }
+        if let Some(offset) = offset {
+            params.push(("offset", offset.to_string()));
+        }
+
+        let url = format!("{}?{}", join_base_url(&self.base_url, "/v1/search/novel"), build_query(&params));
+        Pager::new(self.http_client.clone(), url)
+    }
+
     /// Get user bookmarks novel
     ///
     /// # Arguments
@@ -1392,7 +1649,7 @@ impl AppClient {
             "Fetching user bookmarks novel"
         );
 
-        let url = format!("{}/v1/user/bookmarks/novel", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/user/bookmarks/novel");
         let mut params = Vec::new();
         params.push(("user_id", user_id.to_string()));
         params.push(("restrict", restrict.to_string()));
@@ -1416,66 +1673,209 @@ impl AppClient {
         Ok(bookmarks)
     }
 
-    /// Get webview novel
+    /// Auto-paginating [`user_bookmarks_novel`](Self::user_bookmarks_novel), yielding one [`Novel`] at a time
+    pub fn user_bookmarks_novel_pager(
+        &self,
+        user_id: u64,
+        restrict: FollowRestrict,
+        max_bookmark_id: Option<u64>,
+        tag: Option<&str>,
+    ) -> Pager<UserBookmarksNovelResponse, Novel> {
+        let mut params = vec![("user_id", user_id.to_string()), ("restrict", restrict.to_string())];
+        if let Some(max_bookmark_id) = max_bookmark_id {
+            params.push(("max_bookmark_id", max_bookmark_id.to_string()));
+        }
+        if let Some(tag) = tag {
+            params.push(("tag", tag.to_string()));
+        }
+
+        let url = format!("{}?{}", join_base_url(&self.base_url, "/v1/user/bookmarks/novel"), build_query(&params));
+        Pager::new(self.http_client.clone(), url)
+    }
+
+    /// Get the raw webview HTML for a novel, with no JSON extraction or text processing
     ///
     /// # Arguments
     /// * `novel_id` - Novel ID
-    /// * `raw` - Whether to return raw HTML content
     ///
-    /// # Returns
-    /// Returns webview novel response or raw HTML content
+    /// # Example
+    /// ```rust
+    /// let client = AppClient::new(http_client);
+    /// let html = client.webview_novel_html(12345678).await?;
+    /// ```
+    pub async fn webview_novel_html(&self, novel_id: u64) -> Result<String> {
+        debug!(novel_id = %novel_id, "Fetching raw webview novel HTML");
+
+        let url = join_base_url(&self.base_url, "/webview/v2/novel");
+        let params = [("id", novel_id.to_string()), ("viewer_version", "20221031_ai".to_string())];
+
+        let response = self.http_client.send_request(reqwest::Method::GET, &url, Some(&params)).await?;
+        Ok(response.text().await?)
+    }
+
+    /// Get webview novel, with `novel_text` decoded from the page's embedded
+    /// JSON and stripped of markup
+    ///
+    /// # Arguments
+    /// * `novel_id` - Novel ID
     ///
     /// # Example
     /// ```rust
     /// let client = AppClient::new(http_client);
-    /// let webview_novel = client.webview_novel(
-    ///     12345678,
-    ///     false
-    /// ).await?;
+    /// let webview_novel = client.webview_novel(12345678).await?;
     /// ```
-    pub async fn webview_novel(
-        &self,
-        novel_id: u64,
-        raw: bool,
-    ) -> Result<WebviewNovelResponse> {
-        debug!(
-            novel_id = %novel_id,
-            raw = %raw,
-            "Fetching webview novel"
-        );
+    pub async fn webview_novel(&self, novel_id: u64) -> Result<WebviewNovelResponse> {
+        let html = self.webview_novel_html(novel_id).await?;
 
-        let url = format!("{}/webview/v2/novel", self.base_url);
-        let mut params = Vec::new();
-        params.push(("id", novel_id.to_string()));
-        params.push(("viewer_version", "20221031_ai".to_string()));
+        let json_str = extract_webview_novel_json(&html)?;
+        let mut webview_novel: WebviewNovelResponse = serde_json::from_str(&json_str)?;
+        webview_novel.novel_text = strip_novel_markup(&webview_novel.novel_text);
 
-        let response = self
-            .http_client
-            .send_request(reqwest::Method::GET, &url, Some(&params))
-            .await?;
+        Ok(webview_novel)
+    }
+}
 
-        let text = response.text().await?;
+/// Locate the `{"novel": ..., "novel_text": ..., "isOwnWork": ...}` object
+/// embedded in a webview novel page's inline script
+///
+/// Tries the historical anchor (the enclosing object around the `novel:`
+/// key, found by walking back to its nearest unmatched `{`) first, then
+/// falls back to scanning each `<script>` block for its first brace-balanced
+/// JSON object, so a markup layout change degrades to "try the next script
+/// tag" rather than erroring outright.
+fn extract_webview_novel_json(html: &str) -> Result<String> {
+    if let Some(key_pos) = html.find("novel:") {
+        if let Some(open) = html[..key_pos].rfind('{') {
+            if let Some(json) = balanced_json_object(&html[open..]) {
+                return Ok(json.to_string());
+            }
+        }
+    }
 
-        if raw {
-            // If raw is true, return the HTML content directly.
-            // This requires a different return type, so we'll need to adjust the function signature
-            // or create a new method for raw HTML. For now, we'll assume the non-raw case.
-            // TODO: Handle raw HTML return type if needed.
-            return Err(PixivError::Other("Raw HTML not supported yet".to_string()));
+    for script in script_bodies(html) {
+        if let Some(open) = script.find('{') {
+            if let Some(json) = balanced_json_object(&script[open..]) {
+                return Ok(json.to_string());
+            }
         }
+    }
 
-        // Extract JSON content from HTML
-        let re = regex::Regex::new(r"novel:\s*(\{.+?\}),\s*isOwnWork")?;
-        let captures = re.captures(&text).ok_or_else(|| {
-            PixivError::Other("Failed to extract novel JSON from webview HTML".to_string())
-        })?;
+    Err(PixivError::Unknown("Failed to locate novel JSON in webview HTML".to_string()))
+}
 
-        let json_str = captures.get(1).map_or("", |m| m.as_str());
-        let mut webview_novel: WebviewNovelResponse = serde_json::from_str(json_str)?;
-        webview_novel.novel_text = webview_novel.novel.caption.clone(); // Assuming novel_text is derived from caption
+/// The contents of every `<script>...</script>` block in `html`
+fn script_bodies(html: &str) -> impl Iterator<Item = &str> {
+    let mut rest = html;
+    std::iter::from_fn(move || loop {
+        let open_tag = rest.find("<script")?;
+        let body_start = rest[open_tag..].find('>').map(|p| open_tag + p + 1)?;
+        let body_end = rest[body_start..].find("</script>").map(|p| body_start + p)?;
+        let body = &rest[body_start..body_end];
+        rest = &rest[body_end..];
+        return Some(body);
+    })
+}
 
-        Ok(webview_novel)
+/// Scan `text` (which must start with `{`) for the `}` that balances it,
+/// skipping over braces inside quoted strings, and return the matched slice
+fn balanced_json_object(text: &str) -> Option<&str> {
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in text.char_indices() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&text[..=i]);
+                }
+            }
+            _ => {}
+        }
     }
+
+    None
+}
+
+/// Strip Pixiv's inline novel markup down to plain text
+///
+/// Walks the fragment as a stream of XML/HTML events via `quick-xml`,
+/// keeping only `Text` nodes (with entities unescaped) and turning `<br />`
+/// into a newline; falls back to returning the input unchanged if it isn't
+/// well-formed enough to walk. Pixiv's own bracketed directives
+/// (`[newpage]`, `[chapter:Title]`) aren't XML tags, so they're translated
+/// afterwards with plain string replacement.
+fn strip_novel_markup(raw: &str) -> String {
+    use quick_xml::events::Event;
+    use quick_xml::reader::Reader;
+
+    let mut reader = Reader::from_str(raw);
+    reader.config_mut().check_end_names = false;
+
+    let mut out = String::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Text(e)) => match e.unescape() {
+                Ok(text) => out.push_str(&text),
+                Err(_) => return raw.to_string(),
+            },
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) if e.name().as_ref() == b"br" => out.push('\n'),
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(_) => return raw.to_string(),
+        }
+    }
+
+    replace_chapter_directive(&out.replace("[newpage]", "\n\n")).trim().to_string()
+}
+
+/// Replace every `[chapter:Title]` directive with `Title` on its own line
+fn replace_chapter_directive(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("[chapter:") {
+        out.push_str(&rest[..start]);
+        let after_marker = &rest[start + "[chapter:".len()..];
+        match after_marker.find(']') {
+            Some(end) => {
+                out.push('\n');
+                out.push_str(&after_marker[..end]);
+                out.push('\n');
+                rest = &after_marker[end + 1..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+                break;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Percent-encode and join `params` into a `key=value&key=value` query string
+fn build_query(params: &[(&str, String)]) -> String {
+    params
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, urlencoding::encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
 }
 
 #[cfg(test)]
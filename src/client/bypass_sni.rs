@@ -1,9 +1,11 @@
 use crate::error::{PixivError, Result};
 use crate::models::app::{
-    ContentType, Duration, Filter, IllustDetail, RankingMode, RankingResponse, RecommendedResponse,
+    ContentType, Duration, Filter, Illust, IllustDetail, RankingMode, RankingResponse, RecommendedResponse,
     SearchIllustResponse, SearchTarget, Sort,
 };
 use crate::network::bypass_sni::BypassSniClient;
+use crate::pagination::BypassSniPager;
+use crate::utils::join_base_url;
 use serde::Deserialize;
 use std::collections::HashMap;
 use tracing::debug;
@@ -43,6 +45,17 @@ impl BypassSniAppClient {
         }
     }
 
+    /// Create App API client instance with SNI bypass using a pool of candidate IPs
+    ///
+    /// See [`BypassSniClient::with_ips`] for the failover behavior this enables.
+    pub fn with_ips(ips: &[&str]) -> Result<Self> {
+        let http_client = BypassSniClient::with_ips(ips)?;
+        Ok(Self {
+            http_client,
+            base_url: "https://app-api.pixiv.net".to_string(),
+        })
+    }
+
     /// Create App API client instance with SNI bypass using specified IP
     pub fn with_ip(ip: &str) -> Result<Self> {
         let http_client = BypassSniClient::new(ip)?;
@@ -62,6 +75,20 @@ impl BypassSniAppClient {
         &self.base_url
     }
 
+    /// Get the underlying SNI bypass HTTP client
+    pub fn http_client(&self) -> &BypassSniClient {
+        &self.http_client
+    }
+
+    /// Exchange the stored refresh token for a new access token
+    ///
+    /// See [`BypassSniClient::login_with_refresh_token`]; every subsequent
+    /// request on this client transparently renews the token again once it
+    /// gets close to expiry, so this only needs to be called once up front.
+    pub async fn login_with_refresh_token(&self) -> Result<()> {
+        self.http_client.login_with_refresh_token().await
+    }
+
     /// Get illustration details
     ///
     /// # Arguments
@@ -78,7 +105,7 @@ impl BypassSniAppClient {
     pub async fn illust_detail(&self, illust_id: u64) -> Result<IllustDetail> {
         debug!(illust_id = %illust_id, "Fetching illustration detail with SNI bypass");
         
-        let url = format!("{}/v1/illust/detail", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/illust/detail");
         let params = [("illust_id", illust_id.to_string())];
         
         let response = self
@@ -128,7 +155,7 @@ impl BypassSniAppClient {
             "Fetching illustration ranking with SNI bypass"
         );
         
-        let url = format!("{}/v1/illust/ranking", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/illust/ranking");
         let mut params = Vec::new();
         params.push(("mode", mode.to_string()));
         params.push(("filter", filter.to_string()));
@@ -208,7 +235,7 @@ impl BypassSniAppClient {
             "Fetching recommended illustrations with SNI bypass"
         );
         
-        let url = format!("{}/v1/illust/recommended", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/illust/recommended");
         let mut params = Vec::new();
         params.push(("content_type".to_string(), content_type.to_string()));
         params.push(("include_ranking_label".to_string(), include_ranking_label.to_string()));
@@ -313,7 +340,7 @@ impl BypassSniAppClient {
             "Searching illustrations with SNI bypass"
         );
         
-        let url = format!("{}/v1/search/illust", self.base_url);
+        let url = join_base_url(&self.base_url, "/v1/search/illust");
         let mut params = Vec::new();
         params.push(("word", word.to_string()));
         params.push(("search_target", search_target.to_string()));
@@ -347,9 +374,71 @@ impl BypassSniAppClient {
         
         let text = response.text().await?;
         let search_result: SearchIllustResponse = serde_json::from_str(&text)?;
-        
+
         Ok(search_result)
     }
+
+    /// Stream every illustration in a ranking list, following `next_url` until exhausted
+    ///
+    /// See [`BypassSniPager`] for how each page refetch re-signs the request
+    /// and shares the same token renewal / rate-limit backoff as
+    /// [`Self::illust_ranking`].
+    pub fn illust_ranking_stream(
+        &self,
+        mode: RankingMode,
+        filter: Filter,
+        date: Option<&str>,
+    ) -> BypassSniPager<RankingResponse, Illust> {
+        let mut query = build_query(&[("mode", mode.to_string()), ("filter", filter.to_string())]);
+        if let Some(date) = date {
+            query.push_str(&format!("&date={}", urlencoding::encode(date)));
+        }
+        let url = format!("{}?{}", join_base_url(&self.base_url, "/v1/illust/ranking"), query);
+        BypassSniPager::new(self.http_client.clone(), url)
+    }
+
+    /// Stream every illustration in a recommended feed, following `next_url` until exhausted
+    pub fn illust_recommended_stream(
+        &self,
+        content_type: ContentType,
+        include_ranking_label: bool,
+        filter: Filter,
+    ) -> BypassSniPager<RecommendedResponse, Illust> {
+        let query = build_query(&[
+            ("content_type", content_type.to_string()),
+            ("include_ranking_label", include_ranking_label.to_string()),
+            ("filter", filter.to_string()),
+        ]);
+        let url = format!("{}?{}", join_base_url(&self.base_url, "/v1/illust/recommended"), query);
+        BypassSniPager::new(self.http_client.clone(), url)
+    }
+
+    /// Stream every illustration in a search result, following `next_url` until exhausted
+    pub fn search_illust_stream(
+        &self,
+        word: &str,
+        search_target: SearchTarget,
+        sort: Sort,
+        filter: Filter,
+    ) -> BypassSniPager<SearchIllustResponse, Illust> {
+        let query = build_query(&[
+            ("word", word.to_string()),
+            ("search_target", search_target.to_string()),
+            ("sort", sort.to_string()),
+            ("filter", filter.to_string()),
+        ]);
+        let url = format!("{}?{}", join_base_url(&self.base_url, "/v1/search/illust"), query);
+        BypassSniPager::new(self.http_client.clone(), url)
+    }
+}
+
+/// Percent-encode and join `params` into a `key=value&key=value` query string
+fn build_query(params: &[(&str, String)]) -> String {
+    params
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, urlencoding::encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
 }
 
 #[cfg(test)]
@@ -367,4 +456,27 @@ mod tests {
         let result = BypassSniAppClient::with_ip("invalid_ip");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_illust_recommended_stream_respects_prefixed_base_url() {
+        let mut client = BypassSniAppClient::with_ip("210.140.131.145").unwrap();
+        client.set_base_url("https://proxy.example.com/pixiv".to_string());
+
+        let pager = client.illust_recommended_stream(ContentType::Illust, false, Filter::ForIOS);
+
+        assert!(pager
+            .first_url()
+            .unwrap()
+            .starts_with("https://proxy.example.com/pixiv/v1/illust/recommended?"));
+    }
+
+    #[test]
+    fn test_search_illust_stream_respects_trailing_slash_base_url() {
+        let mut client = BypassSniAppClient::with_ip("210.140.131.145").unwrap();
+        client.set_base_url("https://app-api.pixiv.net/".to_string());
+
+        let pager = client.search_illust_stream("cat", SearchTarget::PartialMatchForTags, Sort::DateDesc, Filter::ForIOS);
+
+        assert!(pager.first_url().unwrap().starts_with("https://app-api.pixiv.net/v1/search/illust?"));
+    }
 }
\ No newline at end of file
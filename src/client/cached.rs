@@ -0,0 +1,286 @@
+//! Persistent, SQLite-backed caching wrapper around [`AppClient`]
+//!
+//! Wraps a handful of read-heavy endpoints in a cache-or-fetch-and-ingest
+//! cycle against a [`RequestStore`], so repeated reads (and fully offline
+//! browsing of already-fetched data) don't re-hit Pixiv. Opt-in and
+//! additive: existing [`AppClient`] call sites are unaffected, and
+//! [`Self::inner`] reaches the wrapped client for everything this wrapper
+//! doesn't cache.
+//!
+//! When a live fetch fails and a cached copy exists (even a stale one),
+//! that copy is served instead of the error (stale-if-error), so transient
+//! outages degrade to serving slightly old data rather than failing
+//! outright. [`Self::with_cached_endpoints`] restricts which endpoints are
+//! cached at all; `refresh_*` methods force a live fetch that still updates
+//! the store.
+
+use crate::client::app::AppClient;
+use crate::error::Result;
+use crate::models::app::{
+    Duration as AppDuration, Filter, IllustDetail, Novel, SearchIllustResponse, SearchTarget, Sort,
+    TrendingTagsResponse, UgoiraMetadataResponse, UserNovelsResponse,
+};
+use crate::store::{request_key, RequestStore};
+use crate::utils::join_base_url;
+use serde::de::DeserializeOwned;
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+
+/// Default TTL applied to a cached endpoint with no per-endpoint override
+const DEFAULT_TTL: Duration = Duration::from_secs(3600);
+
+/// Default row-count threshold that triggers eviction of the oldest entries
+const DEFAULT_MAX_ROWS: usize = 10_000;
+
+/// [`AppClient`] wrapper that persists responses to a local SQLite database
+pub struct CachedAppClient {
+    inner: AppClient,
+    store: RequestStore,
+    default_ttl: Duration,
+    /// `(endpoint substring, ttl)` pairs checked in insertion order; the first match wins
+    endpoint_ttls: Vec<(String, Duration)>,
+    max_rows: usize,
+    /// Endpoint path prefixes eligible for caching; `None` means every endpoint is cached
+    cached_endpoints: Option<HashSet<String>>,
+}
+
+impl CachedAppClient {
+    /// Wrap `inner`, persisting responses to the SQLite database at `db_path`
+    pub fn open(inner: AppClient, db_path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            inner,
+            store: RequestStore::open(db_path)?,
+            default_ttl: DEFAULT_TTL,
+            endpoint_ttls: Vec::new(),
+            max_rows: DEFAULT_MAX_ROWS,
+            cached_endpoints: None,
+        })
+    }
+
+    /// Override the default TTL applied to every cached endpoint
+    pub fn with_default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    /// Override the TTL for URLs containing `path_prefix` (e.g. `/v1/illust/detail`)
+    pub fn with_endpoint_ttl(mut self, path_prefix: impl Into<String>, ttl: Duration) -> Self {
+        self.endpoint_ttls.push((path_prefix.into(), ttl));
+        self
+    }
+
+    /// Override the row-count threshold that triggers eviction of the oldest entries
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Restrict caching to endpoints matching one of these path prefixes
+    /// (e.g. `/v1/illust/detail`); every other endpoint always hits the
+    /// network. Defaults to caching every endpoint this wrapper exposes.
+    pub fn with_cached_endpoints(mut self, prefixes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.cached_endpoints = Some(prefixes.into_iter().map(Into::into).collect());
+        self
+    }
+
+    fn ttl_for(&self, endpoint: &str) -> Duration {
+        self.endpoint_ttls
+            .iter()
+            .find(|(prefix, _)| endpoint.contains(prefix.as_str()))
+            .map(|(_, ttl)| *ttl)
+            .unwrap_or(self.default_ttl)
+    }
+
+    fn is_cacheable(&self, endpoint: &str) -> bool {
+        match &self.cached_endpoints {
+            None => true,
+            Some(allowed) => allowed.iter().any(|prefix| endpoint.contains(prefix.as_str())),
+        }
+    }
+
+    /// Serve `endpoint`/`params` from the store if fresh, otherwise fetch
+    /// through the wrapped client's [`crate::network::HttpClient`], ingest
+    /// the response, and evict the oldest rows if over the configured cap
+    ///
+    /// `envelope_key`, when set, is the field the raw JSON response wraps
+    /// its payload in (e.g. `novel_detail`'s `{"novel": {...}}`) and is
+    /// unwrapped before the value is stored or deserialized, matching
+    /// [`AppClient`]'s own unwrap for that endpoint.
+    ///
+    /// If `endpoint` isn't in [`Self::with_cached_endpoints`]'s allowlist,
+    /// always fetches live and never reads or writes the store. If the live
+    /// fetch fails and a (possibly stale) cached copy exists, that copy is
+    /// returned instead of propagating the error (stale-if-error).
+    async fn fetch_cached<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        params: &[(&str, String)],
+        illust_id: Option<u64>,
+        envelope_key: Option<&str>,
+    ) -> Result<T> {
+        if !self.is_cacheable(endpoint) {
+            return self.fetch_live(endpoint, params, envelope_key).await;
+        }
+
+        let key = request_key("GET", endpoint, params);
+
+        if let Some(cached) = self.store.get(&key, self.ttl_for(endpoint))? {
+            return Ok(serde_json::from_value(cached)?);
+        }
+
+        match self.fetch_live_value(endpoint, params, envelope_key).await {
+            Ok(value) => {
+                self.store.put(&key, endpoint, illust_id, &value)?;
+                self.store.evict_oldest(self.max_rows)?;
+                Ok(serde_json::from_value(value)?)
+            }
+            Err(e) => match self.store.get_stale(&key)? {
+                Some(stale) => Ok(serde_json::from_value(stale)?),
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Force a live fetch for `endpoint`/`params`, bypassing and then
+    /// refreshing the cached copy (if the endpoint is cacheable at all)
+    ///
+    /// See [`Self::fetch_cached`] for `envelope_key`.
+    async fn refresh_cached<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        params: &[(&str, String)],
+        illust_id: Option<u64>,
+        envelope_key: Option<&str>,
+    ) -> Result<T> {
+        let value = self.fetch_live_value(endpoint, params, envelope_key).await?;
+        if self.is_cacheable(endpoint) {
+            let key = request_key("GET", endpoint, params);
+            self.store.put(&key, endpoint, illust_id, &value)?;
+            self.store.evict_oldest(self.max_rows)?;
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+
+    async fn fetch_live<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        params: &[(&str, String)],
+        envelope_key: Option<&str>,
+    ) -> Result<T> {
+        Ok(serde_json::from_value(self.fetch_live_value(endpoint, params, envelope_key).await?)?)
+    }
+
+    async fn fetch_live_value(
+        &self,
+        endpoint: &str,
+        params: &[(&str, String)],
+        envelope_key: Option<&str>,
+    ) -> Result<serde_json::Value> {
+        let url = join_base_url(self.inner.base_url(), endpoint);
+        let response = self.inner.http_client().send_request(reqwest::Method::GET, &url, Some(params)).await?;
+        let text = response.text().await?;
+        let value: serde_json::Value = serde_json::from_str(&text)?;
+        Ok(match envelope_key {
+            Some(key) => value[key].clone(),
+            None => value,
+        })
+    }
+
+    /// Get illustration details, serving from the local store when fresh
+    pub async fn illust_detail(&self, illust_id: u64) -> Result<IllustDetail> {
+        let params = [("illust_id", illust_id.to_string())];
+        self.fetch_cached("/v1/illust/detail", &params, Some(illust_id), None).await
+    }
+
+    /// Search illustrations, serving from the local store when fresh
+    ///
+    /// Takes the same parameters as [`AppClient::search_illust`]; see its docs for semantics.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn search_illust(
+        &self,
+        word: &str,
+        search_target: SearchTarget,
+        sort: Sort,
+        duration: Option<AppDuration>,
+        start_date: Option<&str>,
+        end_date: Option<&str>,
+        filter: Filter,
+        search_ai_type: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<SearchIllustResponse> {
+        let mut params = vec![
+            ("word", word.to_string()),
+            ("search_target", search_target.to_string()),
+            ("sort", sort.to_string()),
+            ("filter", filter.to_string()),
+        ];
+        if let Some(duration) = duration {
+            params.push(("duration", duration.to_string()));
+        }
+        if let Some(start_date) = start_date {
+            params.push(("start_date", start_date.to_string()));
+        }
+        if let Some(end_date) = end_date {
+            params.push(("end_date", end_date.to_string()));
+        }
+        if let Some(search_ai_type) = search_ai_type {
+            params.push(("search_ai_type", search_ai_type.to_string()));
+        }
+        if let Some(offset) = offset {
+            params.push(("offset", offset.to_string()));
+        }
+
+        self.fetch_cached("/v1/search/illust", &params, None, None).await
+    }
+
+    /// Get ugoira metadata, serving from the local store when fresh
+    pub async fn ugoira_metadata(&self, illust_id: u64) -> Result<UgoiraMetadataResponse> {
+        let params = [("illust_id", illust_id.to_string())];
+        self.fetch_cached("/v1/ugoira/metadata", &params, Some(illust_id), None).await
+    }
+
+    /// Get novel details, serving from the local store when fresh
+    pub async fn novel_detail(&self, novel_id: u64) -> Result<Novel> {
+        let params = [("novel_id", novel_id.to_string())];
+        self.fetch_cached("/v2/novel/detail", &params, None, Some("novel")).await
+    }
+
+    /// Force-refresh novel details from the network and update the store
+    pub async fn refresh_novel_detail(&self, novel_id: u64) -> Result<Novel> {
+        let params = [("novel_id", novel_id.to_string())];
+        self.refresh_cached("/v2/novel/detail", &params, None, Some("novel")).await
+    }
+
+    /// Get a user's novels, serving from the local store when fresh
+    pub async fn user_novels(&self, user_id: u64, filter: Filter, offset: Option<u32>) -> Result<UserNovelsResponse> {
+        let mut params = vec![("user_id", user_id.to_string()), ("filter", filter.to_string())];
+        if let Some(offset) = offset {
+            params.push(("offset", offset.to_string()));
+        }
+
+        self.fetch_cached("/v1/user/novels", &params, None, None).await
+    }
+
+    /// Get trending illustration tags, serving from the local store when fresh
+    pub async fn trending_tags_illust(&self, filter: Filter) -> Result<TrendingTagsResponse> {
+        let params = [("filter", filter.to_string())];
+        self.fetch_cached("/v1/trending-tags/illust", &params, None, None).await
+    }
+
+    /// Force-refresh illustration details from the network and update the store
+    pub async fn refresh_illust_detail(&self, illust_id: u64) -> Result<IllustDetail> {
+        let params = [("illust_id", illust_id.to_string())];
+        self.refresh_cached("/v1/illust/detail", &params, Some(illust_id), None).await
+    }
+
+    /// Remove every cached row associated with `illust_id`
+    pub fn invalidate(&self, illust_id: u64) -> Result<()> {
+        self.store.invalidate(illust_id)
+    }
+
+    /// The wrapped client, for calls this wrapper doesn't cache
+    pub fn inner(&self) -> &AppClient {
+        &self.inner
+    }
+}
@@ -1,9 +1,11 @@
 use crate::error::PixivError;
 use crate::network::HttpClient;
 use crate::models::public::{
-    PublicSearchResponse, PublicUserDetail, PublicUserIllusts, PublicUserBookmarks,
+    PublicIllust, PublicSearchResponse, PublicUserDetail, PublicUserIllusts, PublicUserBookmarks,
     SearchTarget, Sort, Duration, Filter, Restrict
 };
+use crate::pagination::Pager;
+use crate::utils::join_base_url;
 use reqwest;
 use serde_json;
 use tracing::debug;
@@ -41,7 +43,7 @@ impl PublicClient {
             ("filter", filter.to_string()),
         ];
 
-        let url = format!("{}{}", self.http_client.base_url(), "/v1/user/detail");
+        let url = join_base_url(self.http_client.base_url(), "/v1/user/detail");
         let response = self
             .http_client
             .send_request(reqwest::Method::GET, &url, Some(&params))
@@ -108,7 +110,7 @@ impl PublicClient {
             params.push(("search_ai_type", search_ai_type.to_string()));
         }
 
-        let url = format!("{}{}", self.http_client.base_url(), "/v1/search/illust");
+        let url = join_base_url(self.http_client.base_url(), "/v1/search/illust");
         let response = self
             .http_client
             .send_request(reqwest::Method::GET, &url, Some(&params))
@@ -116,10 +118,58 @@ impl PublicClient {
         
         let text = response.text().await?;
         let search_result: PublicSearchResponse = serde_json::from_str(&text)?;
-        
+
         Ok(search_result)
     }
 
+    /// Auto-paginating stream of every [`PublicIllust`] matching a search, following `next_url` transparently
+    ///
+    /// Takes the same parameters as [`Self::search_illust`]; see its docs for defaults.
+    /// Lazy: the first page isn't fetched until the stream is polled, and each
+    /// following page is only fetched once its predecessor's items are drained.
+    /// A page request that fails is yielded as an `Err` item (rather than being
+    /// swallowed), after which the stream ends.
+    pub fn search_illust_pager(
+        &self,
+        word: &str,
+        search_target: Option<SearchTarget>,
+        sort: Option<Sort>,
+        duration: Option<Duration>,
+        start_date: Option<&str>,
+        end_date: Option<&str>,
+        filter: Option<Filter>,
+        offset: Option<u32>,
+        search_ai_type: Option<u32>,
+    ) -> Pager<PublicSearchResponse, PublicIllust> {
+        let search_target = search_target.unwrap_or(SearchTarget::PartialMatchForTags);
+        let sort = sort.unwrap_or(Sort::DateDesc);
+        let filter = filter.unwrap_or(Filter::ForIOS);
+
+        let mut query = vec![
+            ("word", word.to_string()),
+            ("search_target", search_target.to_string()),
+            ("sort", sort.to_string()),
+            ("filter", filter.to_string()),
+        ];
+        if let Some(duration) = duration {
+            query.push(("duration", duration.to_string()));
+        }
+        if let Some(start_date) = start_date {
+            query.push(("start_date", start_date.to_string()));
+        }
+        if let Some(end_date) = end_date {
+            query.push(("end_date", end_date.to_string()));
+        }
+        if let Some(offset) = offset {
+            query.push(("offset", offset.to_string()));
+        }
+        if let Some(search_ai_type) = search_ai_type {
+            query.push(("search_ai_type", search_ai_type.to_string()));
+        }
+
+        let url = format!("{}?{}", join_base_url(self.http_client.base_url(), "/v1/search/illust"), build_query(&query));
+        Pager::new(self.http_client.clone(), url)
+    }
 
     /// Get user works list
     ///
@@ -143,7 +193,7 @@ impl PublicClient {
             ("offset", offset.to_string()),
         ];
 
-        let url = format!("{}{}", self.http_client.base_url(), "/v1/user/illusts");
+        let url = join_base_url(self.http_client.base_url(), "/v1/user/illusts");
         let response = self
             .http_client
             .send_request(reqwest::Method::GET, &url, Some(&params))
@@ -151,10 +201,21 @@ impl PublicClient {
         
         let text = response.text().await?;
         let user_illusts: PublicUserIllusts = serde_json::from_str(&text)?;
-        
+
         Ok(user_illusts)
     }
 
+    /// Auto-paginating stream of every [`PublicIllust`] a user has posted, following `next_url` transparently
+    ///
+    /// Lazy, and surfaces a failed page fetch as an `Err` item rather than
+    /// aborting silently; see [`Self::search_illust_pager`] for details.
+    pub fn user_illusts_pager(&self, user_id: u64, offset: Option<u32>) -> Pager<PublicUserIllusts, PublicIllust> {
+        let offset = offset.unwrap_or(0);
+        let query = vec![("user_id", user_id.to_string()), ("offset", offset.to_string())];
+        let url = format!("{}?{}", join_base_url(self.http_client.base_url(), "/v1/user/illusts"), build_query(&query));
+        Pager::new(self.http_client.clone(), url)
+    }
+
     /// Get user bookmarked illustrations
     ///
     /// # Arguments
@@ -181,7 +242,7 @@ impl PublicClient {
             ("offset", offset.to_string()),
         ];
 
-        let url = format!("{}{}", self.http_client.base_url(), "/v1/user/bookmarks/illust");
+        let url = join_base_url(self.http_client.base_url(), "/v1/user/bookmarks/illust");
         let response = self
             .http_client
             .send_request(reqwest::Method::GET, &url, Some(&params))
@@ -189,7 +250,37 @@ impl PublicClient {
         
         let text = response.text().await?;
         let user_bookmarks: PublicUserBookmarks = serde_json::from_str(&text)?;
-        
+
         Ok(user_bookmarks)
     }
+
+    /// Auto-paginating stream of every bookmarked [`PublicIllust`] for a user, following `next_url` transparently
+    ///
+    /// Lazy, and surfaces a failed page fetch as an `Err` item rather than
+    /// aborting silently; see [`Self::search_illust_pager`] for details.
+    pub fn user_bookmarks_illust_pager(
+        &self,
+        user_id: u64,
+        restrict: Option<Restrict>,
+        offset: Option<u32>,
+    ) -> Pager<PublicUserBookmarks, PublicIllust> {
+        let restrict = restrict.unwrap_or(Restrict::Public);
+        let offset = offset.unwrap_or(0);
+        let query = vec![
+            ("user_id", user_id.to_string()),
+            ("restrict", restrict.to_string()),
+            ("offset", offset.to_string()),
+        ];
+        let url = format!("{}?{}", join_base_url(self.http_client.base_url(), "/v1/user/bookmarks/illust"), build_query(&query));
+        Pager::new(self.http_client.clone(), url)
+    }
+}
+
+/// Percent-encode and join `params` into a `key=value&key=value` query string
+fn build_query(params: &[(&str, String)]) -> String {
+    params
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, urlencoding::encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
 }
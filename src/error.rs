@@ -1,6 +1,7 @@
 use thiserror::Error;
 use std::net::AddrParseError;
 use std::collections::HashMap;
+use std::time::Duration;
 
 /// API error codes
 #[derive(Error, Debug, Clone)]
@@ -119,10 +120,41 @@ pub enum PixivError {
     /// Public API error
     #[error("Public API error: {0}")]
     PublicApiError(String),
-    
+
     /// SNI bypass error
     #[error("SNI bypass error: {0}")]
     SniBypassError(#[from] SniBypassError),
+
+    /// Ugoira decoding or re-encoding error
+    #[error("Ugoira error: {0}")]
+    UgoiraError(String),
+
+    /// Search query DSL parse error
+    #[error("Query parse error: {0}")]
+    QueryParseError(#[from] crate::query::QueryParseError),
+
+    /// Terminal image preview error
+    #[cfg(feature = "preview")]
+    #[error("Preview error: {0}")]
+    PreviewError(String),
+
+    /// SQLite-backed response store error
+    #[cfg(feature = "sqlite-cache")]
+    #[error("Store error: {0}")]
+    StoreError(String),
+
+    /// Request was rate-limited and automatic retry is disabled
+    ///
+    /// Distinct from [`PixivError::ApiErrorWithDetails`], which is returned
+    /// when automatic retry is enabled but the configured attempt budget is
+    /// exhausted: this variant means no retry was attempted at all, so the
+    /// throttling is purely transient from the caller's point of view.
+    #[error("Rate limited, retry after {retry_after:?}")]
+    RateLimited {
+        /// How long to wait before retrying, per the `Retry-After` header
+        /// (or a computed exponential backoff when the header is absent)
+        retry_after: Duration,
+    },
 }
 
 /// Network related errors
@@ -139,6 +171,15 @@ pub enum NetworkError {
     /// Invalid URL
     #[error("Invalid URL: {0}")]
     InvalidUrl(String),
+
+    /// A negotiated `Content-Encoding` could not be decoded
+    #[error("Failed to decompress {encoding} response body: {source}")]
+    DecompressionError {
+        /// The `Content-Encoding` that failed to decode (`gzip`, `deflate`, or `br`)
+        encoding: String,
+        /// The underlying decoder error
+        source: std::io::Error,
+    },
 }
 
 /// SNI bypass related errors
@@ -0,0 +1,465 @@
+//! In-memory, offline full-text search over a collected corpus
+//!
+//! [`SearchIndex`] ingests [`Illust`]/[`Novel`] items (via their
+//! [`NormalizedWork`] conversion) and builds an inverted index over each
+//! document's title, caption, and tag names/translations, so a downloaded or
+//! cached corpus can be searched without hitting the API again. Ranking is
+//! BM25, with tag matches given a configurable score boost over body
+//! matches, and query terms also fuzzy-match indexed terms within edit
+//! distance 1 so small typos don't return nothing.
+
+use crate::models::app::Illust;
+use crate::models::work::NormalizedWork;
+use std::collections::{HashMap, HashSet};
+
+/// ID of an indexed document, matching `Illust`/`Novel`/`Work::id()`
+pub type WorkId = u64;
+
+/// Tuning knobs for [`SearchIndex`]
+#[derive(Debug, Clone)]
+pub struct IndexOptions {
+    /// BM25 term-frequency saturation parameter
+    pub k1: f64,
+    /// BM25 document-length normalization parameter
+    pub b: f64,
+    /// Multiplier applied to a term's score when it matches a tag rather than the title/caption
+    pub tag_boost: f64,
+    /// Whether query terms also match indexed terms within edit distance 1
+    pub typo_tolerance: bool,
+}
+
+impl Default for IndexOptions {
+    fn default() -> Self {
+        Self { k1: 1.2, b: 0.75, tag_boost: 2.0, typo_tolerance: true }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Posting {
+    doc: WorkId,
+    term_freq: u32,
+}
+
+/// A scored match returned by [`SearchIndex::search`]
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    /// Matched document ID
+    pub id: WorkId,
+    /// The matched document
+    pub work: NormalizedWork,
+    /// BM25 score, higher is more relevant
+    pub score: f64,
+}
+
+/// Inverted index over a collected corpus of [`Illust`]/[`Novel`] items
+#[derive(Debug, Clone)]
+pub struct SearchIndex {
+    postings: HashMap<String, Vec<Posting>>,
+    tag_docs: HashMap<String, HashSet<WorkId>>,
+    doc_lengths: HashMap<WorkId, usize>,
+    documents: HashMap<WorkId, NormalizedWork>,
+    total_length: usize,
+    options: IndexOptions,
+}
+
+impl SearchIndex {
+    /// Create an empty index with default [`IndexOptions`]
+    pub fn new() -> Self {
+        Self::with_options(IndexOptions::default())
+    }
+
+    /// Create an empty index with custom [`IndexOptions`]
+    pub fn with_options(options: IndexOptions) -> Self {
+        Self {
+            postings: HashMap::new(),
+            tag_docs: HashMap::new(),
+            doc_lengths: HashMap::new(),
+            documents: HashMap::new(),
+            total_length: 0,
+            options,
+        }
+    }
+
+    /// Number of documents in the index
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Whether the index holds no documents
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Ingest one document, tokenizing its title, caption, and tags
+    pub fn add(&mut self, work: impl Into<NormalizedWork>) {
+        let work = work.into();
+        let id = work.id;
+
+        let mut term_freq: HashMap<String, u32> = HashMap::new();
+        let mut tag_terms: HashSet<String> = HashSet::new();
+
+        for token in tokenize(&work.title) {
+            *term_freq.entry(token).or_insert(0) += 1;
+        }
+        for token in tokenize(&work.caption) {
+            *term_freq.entry(token).or_insert(0) += 1;
+        }
+        for tag in &work.tags {
+            for token in tokenize(&tag.name) {
+                *term_freq.entry(token.clone()).or_insert(0) += 1;
+                tag_terms.insert(token);
+            }
+            if let Some(translated) = &tag.translated_name {
+                for token in tokenize(translated) {
+                    *term_freq.entry(token.clone()).or_insert(0) += 1;
+                    tag_terms.insert(token);
+                }
+            }
+        }
+
+        let doc_length: usize = term_freq.values().map(|&f| f as usize).sum();
+        self.total_length += doc_length;
+        self.doc_lengths.insert(id, doc_length);
+
+        for (term, freq) in term_freq {
+            self.postings.entry(term.clone()).or_default().push(Posting { doc: id, term_freq: freq });
+            if tag_terms.contains(&term) {
+                self.tag_docs.entry(term).or_default().insert(id);
+            }
+        }
+
+        self.documents.insert(id, work);
+    }
+
+    /// Ingest every item from an iterator of anything convertible to [`NormalizedWork`]
+    pub fn extend(&mut self, works: impl IntoIterator<Item = impl Into<NormalizedWork>>) {
+        for work in works {
+            self.add(work);
+        }
+    }
+
+    /// Ingest a batch of [`Illust`] records, e.g. straight from an
+    /// [`crate::client::app::AppClient`] response
+    pub fn ingest(&mut self, illusts: &[Illust]) {
+        self.extend(illusts.iter().cloned());
+    }
+
+    /// Rebuild an index from every `/v1/illust/detail` response already held
+    /// in `store`, reusing the [`crate::store::RequestStore`]'s own
+    /// [`crate::error::PixivError::JsonError`]-surfacing deserialization
+    /// rather than persisting a separate snapshot of the index itself
+    #[cfg(feature = "sqlite-cache")]
+    pub fn rebuild_from_store(store: &crate::store::RequestStore) -> crate::error::Result<Self> {
+        let mut index = Self::new();
+        for body in store.list_by_endpoint("/v1/illust/detail")? {
+            let detail: crate::models::app::IllustDetail = serde_json::from_value(body)?;
+            index.add(detail.illust);
+        }
+        Ok(index)
+    }
+
+    /// Search the index, returning up to `limit` results sorted by BM25
+    /// score descending (ties broken by `total_bookmarks` descending)
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchResult> {
+        if self.documents.is_empty() {
+            return Vec::new();
+        }
+
+        let avgdl = self.total_length as f64 / self.documents.len() as f64;
+        let mut scores: HashMap<WorkId, f64> = HashMap::new();
+
+        for query_term in tokenize(query) {
+            for term in self.matching_terms(&query_term) {
+                let Some(postings) = self.postings.get(&term) else { continue };
+                let df = postings.len();
+                let idf = ((self.documents.len() as f64 - df as f64 + 0.5) / (df as f64 + 0.5) + 1.0).ln();
+
+                for posting in postings {
+                    let dl = self.doc_lengths.get(&posting.doc).copied().unwrap_or(0) as f64;
+                    let tf = posting.term_freq as f64;
+                    let denom = tf + self.options.k1 * (1.0 - self.options.b + self.options.b * dl / avgdl);
+                    let mut term_score = idf * (tf * (self.options.k1 + 1.0)) / denom;
+
+                    if self.tag_docs.get(&term).is_some_and(|docs| docs.contains(&posting.doc)) {
+                        term_score *= self.options.tag_boost;
+                    }
+
+                    *scores.entry(posting.doc).or_insert(0.0) += term_score;
+                }
+            }
+        }
+
+        let mut results: Vec<SearchResult> = scores
+            .into_iter()
+            .filter_map(|(id, score)| self.documents.get(&id).map(|work| SearchResult { id, work: work.clone(), score }))
+            .collect();
+
+        results.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| b.work.total_bookmarks.cmp(&a.work.total_bookmarks))
+        });
+        results.truncate(limit);
+        results
+    }
+
+    /// Every indexed term that exactly matches `query_term`, plus (if typo
+    /// tolerance is enabled) terms within edit distance 1, found via a
+    /// bounded scan over terms sharing the same first character
+    fn matching_terms(&self, query_term: &str) -> Vec<String> {
+        if !self.options.typo_tolerance {
+            return if self.postings.contains_key(query_term) { vec![query_term.to_string()] } else { Vec::new() };
+        }
+
+        let first_char = query_term.chars().next();
+        self.postings
+            .keys()
+            .filter(|term| {
+                term.as_str() == query_term
+                    || (term.chars().next() == first_char
+                        && (term.len() as i64 - query_term.len() as i64).abs() <= 1
+                        && edit_distance_at_most_one(term, query_term))
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+impl Default for SearchIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether `a` and `b` are within Levenshtein edit distance 1 of each other
+fn edit_distance_at_most_one(a: &str, b: &str) -> bool {
+    if a == b {
+        return true;
+    }
+
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > 1 {
+        return false;
+    }
+
+    let (shorter, longer) = if a.len() <= b.len() { (&a, &b) } else { (&b, &a) };
+
+    if shorter.len() == longer.len() {
+        // Same length: count mismatched positions, allow at most one (substitution)
+        shorter.iter().zip(longer.iter()).filter(|(x, y)| x != y).count() <= 1
+    } else {
+        // One shorter: walk both, allow skipping exactly one char in `longer` (insertion/deletion)
+        let mut si = 0;
+        let mut li = 0;
+        let mut skipped = false;
+        while si < shorter.len() && li < longer.len() {
+            if shorter[si] == longer[li] {
+                si += 1;
+                li += 1;
+            } else if !skipped {
+                skipped = true;
+                li += 1;
+            } else {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Split `text` into lowercased search terms
+///
+/// ASCII/alphanumeric runs are lowercased and kept whole; CJK runs (where
+/// whitespace-based tokenization doesn't apply, since Pixiv tags are largely
+/// Japanese) are split into overlapping bigrams instead. Everything else is
+/// treated as a separator.
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut ascii_buf = String::new();
+    let mut cjk_buf = String::new();
+
+    for c in text.chars() {
+        if is_cjk(c) {
+            flush_ascii(&mut ascii_buf, &mut tokens);
+            cjk_buf.push(c);
+        } else if c.is_alphanumeric() {
+            flush_cjk(&mut cjk_buf, &mut tokens);
+            ascii_buf.extend(c.to_lowercase());
+        } else {
+            flush_ascii(&mut ascii_buf, &mut tokens);
+            flush_cjk(&mut cjk_buf, &mut tokens);
+        }
+    }
+    flush_ascii(&mut ascii_buf, &mut tokens);
+    flush_cjk(&mut cjk_buf, &mut tokens);
+
+    tokens
+}
+
+fn flush_ascii(buf: &mut String, tokens: &mut Vec<String>) {
+    if !buf.is_empty() {
+        tokens.push(std::mem::take(buf));
+    }
+}
+
+fn flush_cjk(buf: &mut String, tokens: &mut Vec<String>) {
+    let chars: Vec<char> = buf.chars().collect();
+    match chars.len() {
+        0 => {}
+        1 => tokens.push(chars[0].to_string()),
+        _ => {
+            for pair in chars.windows(2) {
+                tokens.push(pair.iter().collect());
+            }
+        }
+    }
+    buf.clear();
+}
+
+/// Whether `c` falls in a CJK script range (Hiragana, Katakana, or CJK Unified Ideographs)
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{309F}' // Hiragana
+        | '\u{30A0}'..='\u{30FF}' // Katakana
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{3400}'..='\u{4DBF}' // CJK Extension A
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::app::{Illust, ImageUrls, MetaSinglePage, ProfileImageUrls, Tag, User};
+    use crate::models::app::ContentType;
+
+    fn illust(id: u64, title: &str, caption: &str, tags: Vec<Tag>, total_bookmarks: u64) -> Illust {
+        Illust {
+            id,
+            title: title.to_string(),
+            illust_type: ContentType::Illust,
+            image_urls: ImageUrls {
+                square_medium: String::new(),
+                medium: String::new(),
+                large: String::new(),
+            },
+            caption: caption.to_string(),
+            restrict: 0,
+            user: User {
+                id: 1,
+                name: "tester".to_string(),
+                account: "tester".to_string(),
+                profile_image_urls: ProfileImageUrls { medium: String::new() },
+                comment: None,
+                is_followed: None,
+            },
+            tags,
+            tools: vec![],
+            create_date: "2024-01-01T00:00:00+00:00".to_string(),
+            page_count: 1,
+            width: 100,
+            height: 100,
+            sanity_level: 2,
+            x_restrict: 0,
+            series: None,
+            meta_single_page: MetaSinglePage { original_image_url: None },
+            meta_pages: vec![],
+            total_view: 10,
+            total_bookmarks,
+            is_bookmarked: false,
+            visible: true,
+            is_muted: false,
+            illust_ai_type: 0,
+            illust_book_style: 0,
+            total_comments: None,
+            comment_access_control: None,
+            restriction_attributes: None,
+        }
+    }
+
+    #[test]
+    fn test_search_ranks_tag_matches_above_body_matches() {
+        let mut index = SearchIndex::new();
+        index.add(illust(1, "a sunny day", "just a landscape", vec![], 5));
+        index.add(illust(
+            2,
+            "untitled",
+            "no relevant words here",
+            vec![Tag { name: "landscape".to_string(), translated_name: None }],
+            5,
+        ));
+
+        let results = index.search("landscape", 10);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, 2, "tag match should outrank body match");
+    }
+
+    #[test]
+    fn test_search_tolerates_single_character_typo() {
+        let mut index = SearchIndex::new();
+        index.add(illust(1, "beautiful sunset", "", vec![], 0));
+
+        let results = index.search("sunswt", 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, 1);
+    }
+
+    #[test]
+    fn test_search_ties_broken_by_total_bookmarks() {
+        let mut index = SearchIndex::new();
+        index.add(illust(1, "cat photo", "", vec![], 1));
+        index.add(illust(2, "cat photo", "", vec![], 100));
+
+        let results = index.search("cat", 10);
+        assert_eq!(results[0].id, 2);
+    }
+
+    #[test]
+    fn test_ingest_accepts_illust_slice_directly() {
+        let mut index = SearchIndex::new();
+        index.ingest(&[illust(1, "a sunny day", "", vec![], 0)]);
+        assert_eq!(index.len(), 1);
+    }
+
+    #[cfg(feature = "sqlite-cache")]
+    #[test]
+    fn test_rebuild_from_store_ingests_cached_illust_details() {
+        use crate::store::{request_key, RequestStore};
+
+        let store = RequestStore::open(":memory:").unwrap();
+        // `Illust` only derives `Deserialize`, so the cached body is built as
+        // raw JSON text here rather than round-tripped through a struct.
+        let body: serde_json::Value = serde_json::from_str(
+            r#"{
+                "illust": {
+                    "id": 1, "title": "beautiful sunset", "type": "illust",
+                    "image_urls": {"square_medium": "", "medium": "", "large": ""},
+                    "caption": "", "restrict": 0,
+                    "user": {"id": 1, "name": "tester", "account": "tester", "profile_image_urls": {"medium": ""}},
+                    "tags": [], "tools": [], "create_date": "2024-01-01T00:00:00+00:00",
+                    "page_count": 1, "width": 100, "height": 100, "sanity_level": 2, "x_restrict": 0,
+                    "series": null, "meta_single_page": {}, "meta_pages": [],
+                    "total_view_count": 10, "total_bookmarks_count": 0,
+                    "is_bookmarked": false, "visible": true, "is_muted": false,
+                    "illust_ai_type": 0, "illust_book_style": 0, "total_comments": null
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let key = request_key("GET", "/v1/illust/detail", &[("illust_id", "1".to_string())]);
+        store.put(&key, "/v1/illust/detail", Some(1), &body).unwrap();
+
+        let index = SearchIndex::rebuild_from_store(&store).unwrap();
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.search("sunset", 10)[0].id, 1);
+    }
+
+    #[test]
+    fn test_tokenize_splits_cjk_into_bigrams() {
+        let tokens = tokenize("東京タワー");
+        assert!(tokens.contains(&"東京".to_string()));
+        assert!(tokens.contains(&"京タ".to_string()));
+    }
+}
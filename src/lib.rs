@@ -34,8 +34,11 @@
 //! ```
 
 pub mod auth;
+pub mod batch;
 pub mod client {
     pub mod app;
+    #[cfg(feature = "sqlite-cache")]
+    pub mod cached;
     pub mod public;
     pub mod bypass_sni;
 }
@@ -43,13 +46,29 @@ pub mod error;
 pub mod models {
     pub mod app;
     pub mod public;
+    pub mod work;
 }
+pub mod download;
+pub mod index;
 pub mod network;
+pub mod pagination;
+#[cfg(feature = "preview")]
+pub mod preview;
+pub mod query;
+#[cfg(feature = "sqlite-cache")]
+pub mod store;
+pub mod ugoira;
 pub mod utils;
 
 // Re-export common types and functions
-pub use auth::{AuthClient, AuthResponse, User as AuthUser};
+pub use auth::{
+    AuthClient, AuthProvider, AuthResponse, FileTokenStore, MemoryTokenStore, PasswordProvider, PkceProvider,
+    TokenStore, User as AuthUser,
+};
+pub use batch::{collect_ordered, fetch_many};
 pub use client::app::AppClient;
+#[cfg(feature = "sqlite-cache")]
+pub use client::cached::CachedAppClient;
 pub use client::public::PublicClient;
 pub use client::bypass_sni::BypassSniAppClient;
 
@@ -81,13 +100,37 @@ pub use models::app::{
     UgoiraFrame, UgoiraMetadata, UgoiraMetadataResponse, User as AppUser, UserFollowerResponse,
     UserFollowingResponse, UserMypixivResponse, UserPreview, ZipUrls,
 };
+pub use models::work::{IllustExtras, NormalizedWork, NovelExtras, Work, WorkKind};
 pub use models::public::{
     PublicIllust, PublicUser, PublicSearchResponse, PublicUserDetail, PublicUserIllusts, PublicUserBookmarks,
     PublicSearchResponse as PublicSearchIllustResponse, SearchTarget as PublicSearchTarget, Sort as PublicSort,
     Restrict as PublicRestrict, ContentType as PublicContentType, Duration as PublicDuration, Filter as PublicFilter
 };
-pub use network::HttpClient;
-pub use utils::{download, extract_extension, format_file_size, parse_qs, safe_filename, set_accept_language};
+pub use network::{
+    AccessLogEntry, AccessLogSink, AuthBackend, CacheConfig, CachedResponse, CallbackAccessLogSink,
+    CompressionEncodings, ConditionalCacheStore, FileAccessLogSink, HttpClient, HttpClientBuilder, HttpResponse,
+    InMemoryConditionalCache, RateLimitInfo, ReauthHandler, RefreshTokenAuth, RetryPolicy, StaticTokenAuth,
+};
+pub use download::{
+    download_illust, download_many, download_novel_authors, download_urls, DownloadEvent, DownloadOptions,
+    DownloadReport, FailedFile,
+};
+pub use index::{IndexOptions, SearchIndex, SearchResult, WorkId};
+pub use pagination::{BypassSniPager, Paginated, Pager};
+pub use query::{parse as parse_query, CmpOp, NumericField, QueryExpr, QueryParseError};
+#[cfg(feature = "preview")]
+pub use preview::{preview_illust, PreviewOptions};
+#[cfg(feature = "sqlite-cache")]
+pub use store::{request_key, RequestStore};
+pub use ugoira::{
+    download_ugoira, download_ugoira_to_file, encode_gif, fetch_and_encode_gif, ugoira_to_animation, UgoiraFormat,
+    UgoiraFrames, ZipVariant,
+};
+pub use utils::{
+    download, download_resumable, extract_extension, format_file_size, join_base_url, log_progress, pad_page,
+    parse_qs, safe_filename, set_accept_language, truncate_title, BatchDownloader, BatchJob, BatchJobReport,
+    FilenameTemplate,
+};
 
 /// Library version information
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -103,12 +146,12 @@ pub fn init_logging() {
     #[cfg(feature = "tracing-subscriber")]
     {
         use tracing_subscriber::{fmt, EnvFilter};
-        
+
         fmt()
             .with_env_filter(EnvFilter::from_default_env())
             .init();
     }
-    
+
     #[cfg(not(feature = "tracing-subscriber"))]
     {
         // If tracing-subscriber is not enabled, do not initialize logger
@@ -117,6 +160,74 @@ pub fn init_logging() {
     }
 }
 
+/// Configuration for the OTLP trace exporter installed by [`init_telemetry`]
+#[derive(Debug, Clone)]
+pub struct TelemetryConfig {
+    /// OTLP collector endpoint, e.g. `http://localhost:4317`
+    pub otlp_endpoint: String,
+    /// Service name attached to every exported span, shown in the collector/UI
+    pub service_name: String,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self { otlp_endpoint: "http://localhost:4317".to_string(), service_name: NAME.to_string() }
+    }
+}
+
+/// Initialize OpenTelemetry OTLP tracing export (feature `otlp`)
+///
+/// Installs a `TraceContextPropagator` and an OTLP exporter pointed at
+/// `config.otlp_endpoint`, so spans opened by [`network::HttpClient::send_request`]
+/// (and anything else instrumented with `tracing`) are shipped to a collector
+/// for distributed-tracing visibility. Falls back to [`init_logging`]'s
+/// fmt-only layer when the `otlp` feature isn't enabled.
+pub fn init_telemetry(config: TelemetryConfig) {
+    #[cfg(feature = "otlp")]
+    {
+        if let Err(e) = otlp::install(config) {
+            eprintln!("Warning: failed to initialize OTLP telemetry, falling back to plain logging: {e}");
+            init_logging();
+        }
+    }
+
+    #[cfg(not(feature = "otlp"))]
+    {
+        let _ = config;
+        eprintln!("Warning: otlp feature not enabled, telemetry will not be exported");
+        init_logging();
+    }
+}
+
+#[cfg(feature = "otlp")]
+mod otlp {
+    use super::TelemetryConfig;
+    use opentelemetry::KeyValue;
+    use opentelemetry_sdk::{propagation::TraceContextPropagator, trace as sdktrace, Resource};
+    use tracing_subscriber::{layer::SubscriberExt, EnvFilter};
+
+    pub(super) fn install(config: TelemetryConfig) -> Result<(), Box<dyn std::error::Error>> {
+        opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
+        let tracer = opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(config.otlp_endpoint))
+            .with_trace_config(
+                sdktrace::config()
+                    .with_resource(Resource::new(vec![KeyValue::new("service.name", config.service_name)])),
+            )
+            .install_batch(opentelemetry_sdk::runtime::Tokio)?;
+
+        let subscriber = tracing_subscriber::registry()
+            .with(EnvFilter::from_default_env())
+            .with(tracing_opentelemetry::layer().with_tracer(tracer))
+            .with(tracing_subscriber::fmt::layer());
+
+        tracing::subscriber::set_global_default(subscriber)?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,4 +237,11 @@ mod tests {
         assert!(!VERSION.is_empty());
         assert_eq!(NAME, "pixiv_rs");
     }
+
+    #[test]
+    fn test_telemetry_config_defaults() {
+        let config = TelemetryConfig::default();
+        assert_eq!(config.otlp_endpoint, "http://localhost:4317");
+        assert_eq!(config.service_name, NAME);
+    }
 }
\ No newline at end of file
@@ -1,21 +1,75 @@
 use serde::Deserialize;
 use std::collections::HashMap;
 
-/// Follow restriction type
-#[derive(Debug, Clone, Copy)]
-pub enum FollowRestrict {
-    /// Public
-    Public,
-    /// Private
-    Private,
-}
-
-impl ToString for FollowRestrict {
-    fn to_string(&self) -> String {
-        match self {
-            FollowRestrict::Public => "public".to_string(),
-            FollowRestrict::Private => "private".to_string(),
+/// Declares a "wire enum": a plain-string API parameter/field that also needs
+/// to round-trip through `serde`.
+///
+/// Generates the enum itself (plus a trailing `Other(String)` variant so
+/// unrecognized values from the API don't fail to parse), `Display` (the
+/// wire string), `FromStr` (infallible, falling back to `Other`), and
+/// `Serialize`/`Deserialize` built on top of those two.
+macro_rules! wire_enum {
+    (
+        $(#[$enum_meta:meta])*
+        pub enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident => $wire:literal
+            ),+ $(,)?
         }
+    ) => {
+        $(#[$enum_meta])*
+        pub enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )+
+            /// Unknown wire value, preserved so future API additions don't fail to parse
+            Other(String),
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $( $name::$variant => write!(f, $wire), )+
+                    $name::Other(s) => write!(f, "{}", s),
+                }
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = std::convert::Infallible;
+
+            fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+                Ok(match s {
+                    $( $wire => $name::$variant, )+
+                    other => $name::Other(other.to_string()),
+                })
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.to_string())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+                Ok(s.parse().unwrap())
+            }
+        }
+    };
+}
+
+wire_enum! {
+    /// Follow restriction type
+    #[derive(Debug, Clone, Default)]
+    pub enum FollowRestrict {
+        #[default]
+        Public => "public",
+        Private => "private",
     }
 }
 
@@ -186,7 +240,7 @@ pub struct UserBookmarkIllust {
     /// Tag list
     pub tags: Vec<Tag>,
     /// Restrict
-    pub restrict: String,
+    pub restrict: FollowRestrict,
 }
 
 /// Illustration new response
@@ -436,7 +490,7 @@ pub struct Illust {
     pub title: String,
     /// Illustration type
     #[serde(rename = "type")]
-    pub illust_type: String,
+    pub illust_type: ContentType,
     /// Image URLs
     pub image_urls: ImageUrls,
     /// Illustration description
@@ -510,7 +564,7 @@ pub struct RankingResponse {
     /// Ranking date
     pub date: Option<String>,
     /// Ranking mode
-    pub mode: Option<String>,
+    pub mode: Option<RankingMode>,
 }
 
 /// Recommendation response
@@ -547,156 +601,106 @@ pub struct SearchIllustResponse {
     pub show_ai: bool,
 }
 
-/// Search target type
-#[derive(Debug, Clone, Copy)]
-pub enum SearchTarget {
-    /// Partial match for tags
-    PartialMatchForTags,
-    /// Exact match for tags
-    ExactMatchForTags,
-    /// Title and caption
-    TitleAndCaption,
-    /// Keyword
-    Keyword,
-}
-
-impl ToString for SearchTarget {
-    fn to_string(&self) -> String {
-        match self {
-            SearchTarget::PartialMatchForTags => "partial_match_for_tags".to_string(),
-            SearchTarget::ExactMatchForTags => "exact_match_for_tags".to_string(),
-            SearchTarget::TitleAndCaption => "title_and_caption".to_string(),
-            SearchTarget::Keyword => "keyword".to_string(),
-        }
+wire_enum! {
+    /// Search target type
+    #[derive(Debug, Clone, Default)]
+    pub enum SearchTarget {
+        /// Partial match for tags
+        #[default]
+        PartialMatchForTags => "partial_match_for_tags",
+        /// Exact match for tags
+        ExactMatchForTags => "exact_match_for_tags",
+        /// Title and caption
+        TitleAndCaption => "title_and_caption",
+        /// Keyword
+        Keyword => "keyword",
     }
 }
 
-/// Sort method
-#[derive(Debug, Clone, Copy)]
-pub enum Sort {
-    /// Date descending
-    DateDesc,
-    /// Date ascending
-    DateAsc,
-    /// Popular descending
-    PopularDesc,
-}
-
-impl ToString for Sort {
-    fn to_string(&self) -> String {
-        match self {
-            Sort::DateDesc => "date_desc".to_string(),
-            Sort::DateAsc => "date_asc".to_string(),
-            Sort::PopularDesc => "popular_desc".to_string(),
-        }
+wire_enum! {
+    /// Sort method
+    #[derive(Debug, Clone, Default)]
+    pub enum Sort {
+        /// Date descending
+        #[default]
+        DateDesc => "date_desc",
+        /// Date ascending
+        DateAsc => "date_asc",
+        /// Popular descending
+        PopularDesc => "popular_desc",
     }
 }
 
-/// Ranking mode
-#[derive(Debug, Clone, Copy)]
-pub enum RankingMode {
-    /// Daily ranking
-    Day,
-    /// Weekly ranking
-    Week,
-    /// Monthly ranking
-    Month,
-    /// Daily male ranking
-    DayMale,
-    /// Daily female ranking
-    DayFemale,
-    /// Weekly original ranking
-    WeekOriginal,
-    /// Weekly rookie ranking
-    WeekRookie,
-    /// Daily manga ranking
-    DayManga,
-    /// Daily R-18 ranking
-    DayR18,
-    /// Daily R-18 male ranking
-    DayMaleR18,
-    /// Daily R-18 female ranking
-    DayFemaleR18,
-    /// Weekly R-18 ranking
-    WeekR18,
-    /// Weekly R-18G ranking
-    WeekR18g,
-}
-
-impl ToString for RankingMode {
-    fn to_string(&self) -> String {
-        match self {
-            RankingMode::Day => "day".to_string(),
-            RankingMode::Week => "week".to_string(),
-            RankingMode::Month => "month".to_string(),
-            RankingMode::DayMale => "day_male".to_string(),
-            RankingMode::DayFemale => "day_female".to_string(),
-            RankingMode::WeekOriginal => "week_original".to_string(),
-            RankingMode::WeekRookie => "week_rookie".to_string(),
-            RankingMode::DayManga => "day_manga".to_string(),
-            RankingMode::DayR18 => "day_r18".to_string(),
-            RankingMode::DayMaleR18 => "day_male_r18".to_string(),
-            RankingMode::DayFemaleR18 => "day_female_r18".to_string(),
-            RankingMode::WeekR18 => "week_r18".to_string(),
-            RankingMode::WeekR18g => "week_r18g".to_string(),
-        }
+wire_enum! {
+    /// Ranking mode
+    #[derive(Debug, Clone, Default)]
+    pub enum RankingMode {
+        /// Daily ranking
+        #[default]
+        Day => "day",
+        /// Weekly ranking
+        Week => "week",
+        /// Monthly ranking
+        Month => "month",
+        /// Daily male ranking
+        DayMale => "day_male",
+        /// Daily female ranking
+        DayFemale => "day_female",
+        /// Weekly original ranking
+        WeekOriginal => "week_original",
+        /// Weekly rookie ranking
+        WeekRookie => "week_rookie",
+        /// Daily manga ranking
+        DayManga => "day_manga",
+        /// Daily R-18 ranking
+        DayR18 => "day_r18",
+        /// Daily R-18 male ranking
+        DayMaleR18 => "day_male_r18",
+        /// Daily R-18 female ranking
+        DayFemaleR18 => "day_female_r18",
+        /// Weekly R-18 ranking
+        WeekR18 => "week_r18",
+        /// Weekly R-18G ranking
+        WeekR18g => "week_r18g",
     }
 }
 
-/// Content type
-#[derive(Debug, Clone, Copy)]
-pub enum ContentType {
-    /// Illustration
-    Illust,
-    /// Manga
-    Manga,
-}
-
-impl ToString for ContentType {
-    fn to_string(&self) -> String {
-        match self {
-            ContentType::Illust => "illust".to_string(),
-            ContentType::Manga => "manga".to_string(),
-        }
+wire_enum! {
+    /// Content type
+    #[derive(Debug, Clone, Default)]
+    pub enum ContentType {
+        /// Illustration
+        #[default]
+        Illust => "illust",
+        /// Manga
+        Manga => "manga",
+        /// Ugoira (animated illustration)
+        Ugoira => "ugoira",
     }
 }
 
-/// Filter type
-#[derive(Debug, Clone, Copy)]
-pub enum Filter {
-    /// iOS filter
-    ForIOS,
-    /// No filter
-    None,
-}
-
-impl ToString for Filter {
-    fn to_string(&self) -> String {
-        match self {
-            Filter::ForIOS => "for_ios".to_string(),
-            Filter::None => "".to_string(),
-        }
+wire_enum! {
+    /// Filter type
+    #[derive(Debug, Clone, Default)]
+    pub enum Filter {
+        /// iOS filter
+        #[default]
+        ForIOS => "for_ios",
+        /// No filter
+        None => "",
     }
 }
 
-/// Search duration
-#[derive(Debug, Clone, Copy)]
-pub enum Duration {
-    /// Within last day
-    WithinLastDay,
-    /// Within last week
-    WithinLastWeek,
-    /// Within last month
-    WithinLastMonth,
-}
-
-impl ToString for Duration {
-    fn to_string(&self) -> String {
-        match self {
-            Duration::WithinLastDay => "within_last_day".to_string(),
-            Duration::WithinLastWeek => "within_last_week".to_string(),
-            Duration::WithinLastMonth => "within_last_month".to_string(),
-        }
+wire_enum! {
+    /// Search duration
+    #[derive(Debug, Clone)]
+    pub enum Duration {
+        /// Within last day
+        WithinLastDay => "within_last_day",
+        /// Within last week
+        WithinLastWeek => "within_last_week",
+        /// Within last month
+        WithinLastMonth => "within_last_month",
     }
 }
 
@@ -823,47 +827,32 @@ pub struct WebviewNovelResponse {
     pub novel_text: String,
 }
 
-/// Search target type for novels
-#[derive(Debug, Clone, Copy)]
-pub enum NovelSearchTarget {
-    /// Partial match for tags
-    PartialMatchForTags,
-    /// Exact match for tags
-    ExactMatchForTags,
-    /// Text
-    Text,
-    /// Keyword
-    Keyword,
-}
-
-impl ToString for NovelSearchTarget {
-    fn to_string(&self) -> String {
-        match self {
-            NovelSearchTarget::PartialMatchForTags => "partial_match_for_tags".to_string(),
-            NovelSearchTarget::ExactMatchForTags => "exact_match_for_tags".to_string(),
-            NovelSearchTarget::Text => "text".to_string(),
-            NovelSearchTarget::Keyword => "keyword".to_string(),
-        }
+wire_enum! {
+    /// Search target type for novels
+    #[derive(Debug, Clone, Default)]
+    pub enum NovelSearchTarget {
+        /// Partial match for tags
+        #[default]
+        PartialMatchForTags => "partial_match_for_tags",
+        /// Exact match for tags
+        ExactMatchForTags => "exact_match_for_tags",
+        /// Text
+        Text => "text",
+        /// Keyword
+        Keyword => "keyword",
     }
 }
 
-/// Follow restriction type for novels
-#[derive(Debug, Clone, Copy)]
-pub enum NovelFollowRestrict {
-    /// Public
-    Public,
-    /// Private
-    Private,
-    /// All
-    All,
-}
-
-impl ToString for NovelFollowRestrict {
-    fn to_string(&self) -> String {
-        match self {
-            NovelFollowRestrict::Public => "public".to_string(),
-            NovelFollowRestrict::Private => "private".to_string(),
-            NovelFollowRestrict::All => "all".to_string(),
-        }
+wire_enum! {
+    /// Follow restriction type for novels
+    #[derive(Debug, Clone, Default)]
+    pub enum NovelFollowRestrict {
+        /// Public
+        #[default]
+        Public => "public",
+        /// Private
+        Private => "private",
+        /// All
+        All => "all",
     }
 }
\ No newline at end of file
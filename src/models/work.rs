@@ -0,0 +1,371 @@
+//! Normalized view over heterogeneous work types
+//!
+//! [`Illust`] and [`Novel`] duplicate a large set of fields (id, title,
+//! caption, user, tags, ...), yet callers that want to treat a mixed feed
+//! (a combined timeline, search results, bookmarks) uniformly have to match
+//! on each type by hand. [`Work`] wraps either one behind a common accessor
+//! surface, and [`NormalizedWork`] flattens that into a single struct that
+//! lifts the shared fields and keeps type-specific extras in
+//! [`IllustExtras`]/[`NovelExtras`].
+
+use crate::models::app::{ContentType, Illust, MetaPage, MetaSinglePage, Novel, Series, Tag, User};
+
+/// Which concrete type a [`Work`] or [`NormalizedWork`] was built from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkKind {
+    /// An illustration or manga
+    Illust,
+    /// A novel
+    Novel,
+}
+
+/// Either an [`Illust`] or a [`Novel`], exposing the fields they share
+#[derive(Debug, Clone)]
+pub enum Work {
+    /// Illustration or manga
+    Illust(Illust),
+    /// Novel
+    Novel(Novel),
+}
+
+impl Work {
+    /// Which concrete type this work wraps
+    pub fn kind(&self) -> WorkKind {
+        match self {
+            Work::Illust(_) => WorkKind::Illust,
+            Work::Novel(_) => WorkKind::Novel,
+        }
+    }
+
+    /// Work ID
+    pub fn id(&self) -> u64 {
+        match self {
+            Work::Illust(i) => i.id,
+            Work::Novel(n) => n.id,
+        }
+    }
+
+    /// Title
+    pub fn title(&self) -> &str {
+        match self {
+            Work::Illust(i) => &i.title,
+            Work::Novel(n) => &n.title,
+        }
+    }
+
+    /// Description/caption
+    pub fn caption(&self) -> &str {
+        match self {
+            Work::Illust(i) => &i.caption,
+            Work::Novel(n) => &n.caption,
+        }
+    }
+
+    /// Restriction level
+    pub fn restrict(&self) -> u32 {
+        match self {
+            Work::Illust(i) => i.restrict,
+            Work::Novel(n) => n.restrict,
+        }
+    }
+
+    /// Author
+    pub fn user(&self) -> &User {
+        match self {
+            Work::Illust(i) => &i.user,
+            Work::Novel(n) => &n.user,
+        }
+    }
+
+    /// Tag list
+    pub fn tags(&self) -> &[Tag] {
+        match self {
+            Work::Illust(i) => &i.tags,
+            Work::Novel(n) => &n.tags,
+        }
+    }
+
+    /// Creation date
+    pub fn create_date(&self) -> &str {
+        match self {
+            Work::Illust(i) => &i.create_date,
+            Work::Novel(n) => &n.create_date,
+        }
+    }
+
+    /// Total view count
+    pub fn total_view(&self) -> u64 {
+        match self {
+            Work::Illust(i) => i.total_view,
+            Work::Novel(n) => n.total_view,
+        }
+    }
+
+    /// Total bookmark count
+    pub fn total_bookmarks(&self) -> u64 {
+        match self {
+            Work::Illust(i) => i.total_bookmarks,
+            Work::Novel(n) => n.total_bookmarks,
+        }
+    }
+
+    /// Whether the current user has bookmarked this work
+    pub fn is_bookmarked(&self) -> bool {
+        match self {
+            Work::Illust(i) => i.is_bookmarked,
+            Work::Novel(n) => n.is_bookmarked,
+        }
+    }
+
+    /// Whether this work is visible
+    pub fn visible(&self) -> bool {
+        match self {
+            Work::Illust(i) => i.visible,
+            Work::Novel(n) => n.visible,
+        }
+    }
+
+    /// Whether this work is muted
+    pub fn is_muted(&self) -> bool {
+        match self {
+            Work::Illust(i) => i.is_muted,
+            Work::Novel(n) => n.is_muted,
+        }
+    }
+
+    /// Series information, if this work belongs to one
+    pub fn series(&self) -> Option<&Series> {
+        match self {
+            Work::Illust(i) => i.series.as_ref(),
+            Work::Novel(n) => n.series.as_ref(),
+        }
+    }
+
+    /// AI-generation type
+    pub fn ai_type(&self) -> u32 {
+        match self {
+            Work::Illust(i) => i.illust_ai_type,
+            Work::Novel(n) => n.novel_ai_type,
+        }
+    }
+}
+
+impl From<Illust> for Work {
+    fn from(illust: Illust) -> Self {
+        Work::Illust(illust)
+    }
+}
+
+impl From<Novel> for Work {
+    fn from(novel: Novel) -> Self {
+        Work::Novel(novel)
+    }
+}
+
+/// Illust-only fields that don't have a `Novel` equivalent
+#[derive(Debug, Clone)]
+pub struct IllustExtras {
+    /// Illustration type (illust/manga/ugoira)
+    pub illust_type: ContentType,
+    /// Page count
+    pub page_count: u32,
+    /// Single-page metadata
+    pub meta_single_page: MetaSinglePage,
+    /// Page metadata, for multi-page works
+    pub meta_pages: Vec<MetaPage>,
+}
+
+/// Novel-only fields that don't have an `Illust` equivalent
+#[derive(Debug, Clone)]
+pub struct NovelExtras {
+    /// Page count
+    pub page_count: u32,
+    /// Text length
+    pub text_length: u32,
+}
+
+/// Flattened, type-erased view of a [`Work`]
+///
+/// Carries the fields `Illust` and `Novel` share directly, and keeps the
+/// fields unique to each behind `Some` in the matching extras field
+/// (`illust_extras` for [`WorkKind::Illust`], `novel_extras` for
+/// [`WorkKind::Novel`]). This makes heterogeneous feeds sortable and
+/// filterable without per-type branching.
+#[derive(Debug, Clone)]
+pub struct NormalizedWork {
+    /// Which concrete type this was normalized from
+    pub kind: WorkKind,
+    /// Work ID
+    pub id: u64,
+    /// Title
+    pub title: String,
+    /// Description/caption
+    pub caption: String,
+    /// Restriction level
+    pub restrict: u32,
+    /// Author
+    pub user: User,
+    /// Tag list
+    pub tags: Vec<Tag>,
+    /// Creation date
+    pub create_date: String,
+    /// Total view count
+    pub total_view: u64,
+    /// Total bookmark count
+    pub total_bookmarks: u64,
+    /// Whether the current user has bookmarked this work
+    pub is_bookmarked: bool,
+    /// Whether this work is visible
+    pub visible: bool,
+    /// Whether this work is muted
+    pub is_muted: bool,
+    /// Series information, if this work belongs to one
+    pub series: Option<Series>,
+    /// AI-generation type
+    pub ai_type: u32,
+    /// Illust-only fields, present when `kind == WorkKind::Illust`
+    pub illust_extras: Option<IllustExtras>,
+    /// Novel-only fields, present when `kind == WorkKind::Novel`
+    pub novel_extras: Option<NovelExtras>,
+}
+
+impl From<Illust> for NormalizedWork {
+    fn from(illust: Illust) -> Self {
+        Self {
+            kind: WorkKind::Illust,
+            id: illust.id,
+            title: illust.title,
+            caption: illust.caption,
+            restrict: illust.restrict,
+            user: illust.user,
+            tags: illust.tags,
+            create_date: illust.create_date,
+            total_view: illust.total_view,
+            total_bookmarks: illust.total_bookmarks,
+            is_bookmarked: illust.is_bookmarked,
+            visible: illust.visible,
+            is_muted: illust.is_muted,
+            series: illust.series,
+            ai_type: illust.illust_ai_type,
+            illust_extras: Some(IllustExtras {
+                illust_type: illust.illust_type,
+                page_count: illust.page_count,
+                meta_single_page: illust.meta_single_page,
+                meta_pages: illust.meta_pages,
+            }),
+            novel_extras: None,
+        }
+    }
+}
+
+impl From<Novel> for NormalizedWork {
+    fn from(novel: Novel) -> Self {
+        Self {
+            kind: WorkKind::Novel,
+            id: novel.id,
+            title: novel.title,
+            caption: novel.caption,
+            restrict: novel.restrict,
+            user: novel.user,
+            tags: novel.tags,
+            create_date: novel.create_date,
+            total_view: novel.total_view,
+            total_bookmarks: novel.total_bookmarks,
+            is_bookmarked: novel.is_bookmarked,
+            visible: novel.visible,
+            is_muted: novel.is_muted,
+            series: novel.series,
+            ai_type: novel.novel_ai_type,
+            illust_extras: None,
+            novel_extras: Some(NovelExtras {
+                page_count: novel.page_count,
+                text_length: novel.text_length,
+            }),
+        }
+    }
+}
+
+impl From<Work> for NormalizedWork {
+    fn from(work: Work) -> Self {
+        match work {
+            Work::Illust(illust) => illust.into(),
+            Work::Novel(novel) => novel.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::app::{ImageUrls, ProfileImageUrls};
+
+    fn sample_user() -> User {
+        User {
+            id: 1,
+            name: "tester".to_string(),
+            account: "tester".to_string(),
+            profile_image_urls: ProfileImageUrls { medium: "https://example.com/a.jpg".to_string() },
+            comment: None,
+            is_followed: None,
+        }
+    }
+
+    fn sample_illust() -> Illust {
+        Illust {
+            id: 1,
+            title: "An illust".to_string(),
+            illust_type: ContentType::Illust,
+            image_urls: ImageUrls {
+                square_medium: "https://example.com/s.jpg".to_string(),
+                medium: "https://example.com/m.jpg".to_string(),
+                large: "https://example.com/l.jpg".to_string(),
+            },
+            caption: "".to_string(),
+            restrict: 0,
+            user: sample_user(),
+            tags: vec![],
+            tools: vec![],
+            create_date: "2024-01-01T00:00:00+00:00".to_string(),
+            page_count: 1,
+            width: 100,
+            height: 100,
+            sanity_level: 2,
+            x_restrict: 0,
+            series: None,
+            meta_single_page: MetaSinglePage { original_image_url: None },
+            meta_pages: vec![],
+            total_view: 10,
+            total_bookmarks: 2,
+            is_bookmarked: false,
+            visible: true,
+            is_muted: false,
+            illust_ai_type: 0,
+            illust_book_style: 0,
+            total_comments: None,
+            comment_access_control: None,
+            restriction_attributes: None,
+        }
+    }
+
+    #[test]
+    fn test_normalized_work_from_illust_preserves_shared_fields() {
+        let illust = sample_illust();
+        let normalized: NormalizedWork = illust.clone().into();
+
+        assert_eq!(normalized.kind, WorkKind::Illust);
+        assert_eq!(normalized.id, illust.id);
+        assert_eq!(normalized.title, illust.title);
+        assert!(normalized.novel_extras.is_none());
+        assert!(normalized.illust_extras.is_some());
+    }
+
+    #[test]
+    fn test_work_accessors_match_underlying_illust() {
+        let illust = sample_illust();
+        let work: Work = illust.clone().into();
+
+        assert_eq!(work.id(), illust.id);
+        assert_eq!(work.title(), illust.title);
+        assert_eq!(work.kind(), WorkKind::Illust);
+    }
+}
@@ -0,0 +1,147 @@
+//! Structured access log for outgoing API requests
+//!
+//! Opt-in via [`super::HttpClient::with_access_log_sink`]. Unlike the
+//! `tracing` debug lines scattered through `public.rs`/`app.rs`, this writes
+//! one stable, parseable entry per call to a configurable sink — a file, or
+//! anything implementing [`AccessLogSink`] — for offline rate-limit analysis
+//! and debugging intermittent `403`/`429` responses.
+
+use crate::error::ApiErrorCode;
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Query parameter keys whose values are replaced with `"<redacted>"` before logging
+const REDACTED_KEYS: &[&str] = &["access_token", "refresh_token", "client_secret", "password", "code", "token"];
+
+/// One structured entry describing a completed (or failed) API call
+#[derive(Debug, Clone, Serialize)]
+pub struct AccessLogEntry {
+    /// RFC 3339 timestamp of when the request was sent
+    pub timestamp: String,
+    /// HTTP method, e.g. `GET`
+    pub method: String,
+    /// Full request URL, with sensitive query parameter values redacted
+    pub url: String,
+    /// HTTP status code, if a response was received at all
+    pub status: Option<u16>,
+    /// Mapped API error code, present only on a non-success response
+    pub error_code: Option<String>,
+    /// Size of the (decoded) response body in bytes
+    pub bytes: usize,
+    /// Wall-clock time spent on the request, including any retries
+    pub duration_ms: u128,
+}
+
+/// Destination for [`AccessLogEntry`] records
+///
+/// Implement this to plug in a custom backend (e.g. a log aggregator);
+/// [`FileAccessLogSink`] covers the common "append JSON lines to a file" case.
+pub trait AccessLogSink: std::fmt::Debug + Send + Sync {
+    /// Record one completed request
+    fn record(&self, entry: &AccessLogEntry);
+}
+
+/// Appends one JSON object per line to a file
+#[derive(Debug)]
+pub struct FileAccessLogSink {
+    file: Mutex<File>,
+}
+
+impl FileAccessLogSink {
+    /// Open (creating if needed) `path` for appending
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file: Mutex::new(file) })
+    }
+}
+
+impl AccessLogSink for FileAccessLogSink {
+    fn record(&self, entry: &AccessLogEntry) {
+        let Ok(line) = serde_json::to_string(entry) else { return };
+        let mut file = self.file.lock().unwrap();
+        let _ = writeln!(file, "{}", line);
+    }
+}
+
+/// Hands each entry to a user-supplied callback, e.g. to forward into an existing logging pipeline
+pub struct CallbackAccessLogSink<F: Fn(&AccessLogEntry) + Send + Sync> {
+    callback: F,
+}
+
+impl<F: Fn(&AccessLogEntry) + Send + Sync> CallbackAccessLogSink<F> {
+    /// Wrap `callback` as an [`AccessLogSink`]
+    pub fn new(callback: F) -> Self {
+        Self { callback }
+    }
+}
+
+impl<F: Fn(&AccessLogEntry) + Send + Sync> std::fmt::Debug for CallbackAccessLogSink<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CallbackAccessLogSink").finish_non_exhaustive()
+    }
+}
+
+impl<F: Fn(&AccessLogEntry) + Send + Sync> AccessLogSink for CallbackAccessLogSink<F> {
+    fn record(&self, entry: &AccessLogEntry) {
+        (self.callback)(entry);
+    }
+}
+
+/// Replace the value of any sensitive query parameter in `url` with `<redacted>`
+pub(crate) fn redact_url(url: &str) -> String {
+    let Some((base, query)) = url.split_once('?') else {
+        return url.to_string();
+    };
+
+    let redacted_query: Vec<String> = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, _)) if REDACTED_KEYS.contains(&key) => format!("{}=<redacted>", key),
+            _ => pair.to_string(),
+        })
+        .collect();
+
+    format!("{}?{}", base, redacted_query.join("&"))
+}
+
+pub(crate) fn build_entry(
+    method: &str,
+    url: &str,
+    status: Option<u16>,
+    error_code: Option<ApiErrorCode>,
+    bytes: usize,
+    elapsed: Duration,
+) -> AccessLogEntry {
+    AccessLogEntry {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        method: method.to_string(),
+        url: redact_url(url),
+        status,
+        error_code: error_code.map(|code| code.to_string()),
+        bytes,
+        duration_ms: elapsed.as_millis(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_url_masks_sensitive_params() {
+        let url = "https://app-api.pixiv.net/v1/user/detail?user_id=1&access_token=secret123";
+        let redacted = redact_url(url);
+
+        assert_eq!(redacted, "https://app-api.pixiv.net/v1/user/detail?user_id=1&access_token=<redacted>");
+    }
+
+    #[test]
+    fn test_redact_url_without_query_is_unchanged() {
+        let url = "https://app-api.pixiv.net/v1/user/detail";
+        assert_eq!(redact_url(url), url);
+    }
+}
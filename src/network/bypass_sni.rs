@@ -1,9 +1,84 @@
 use crate::error::{NetworkError, PixivError, Result};
+use crate::network::RateLimitInfo;
 use reqwest::{Client, Response};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::net::IpAddr;
-use tracing::debug;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Pixiv's official Android app client ID, used by [`BypassSniClient::login_with_refresh_token`]
+const CLIENT_ID: &str = "MOBrBDS8blbauoSck0ZfDbtuzpyT";
+/// Pixiv's official Android app client secret, used by [`BypassSniClient::login_with_refresh_token`]
+const CLIENT_SECRET: &str = "lsACyCD94FhDUtGTXi3QzcFE2uU1hqtDaKeqrdwj";
+/// Token endpoint used by [`BypassSniClient::login_with_refresh_token`]
+const AUTH_URL: &str = "https://oauth.secure.pixiv.net/auth/token";
+/// How close to expiry a token must be before [`BypassSniClient::send_request`] proactively renews it
+const EXPIRY_SKEW: Duration = Duration::from_secs(60);
+/// Default salt mixed into [`BypassSniClient::generate_security_headers`]'s `x-client-hash`,
+/// overridable via [`BypassSniClient::set_hash_secret`] when Pixiv rotates it
+const DEFAULT_HASH_SECRET: &str = "28c1fdd170a5204386cb1313c7077b34f83e4aaf4aa829ce78c231e05b0bae2c";
+/// Default number of retries for a rate-limited request before giving up
+const DEFAULT_MAX_RATE_LIMIT_RETRIES: u32 = 3;
+/// How long a dead IP is excluded from rotation before being tried again
+const IP_COOLDOWN: Duration = Duration::from_secs(300);
+/// Hostname [`BypassSniClient`]'s resolve override pins to a candidate IP
+const PIXIV_APP_API_HOST: &str = "app-api.pixiv.net";
+/// DNS-over-HTTPS resolver used by [`BypassSniClient::refresh_ips_from_doh`]
+const DOH_RESOLVER_URL: &str = "https://cloudflare-dns.com/dns-query";
+
+/// One candidate IP in a [`BypassSniClient`]'s pool, with an optional cooldown
+/// set after a connection/TLS failure
+#[derive(Debug, Clone)]
+struct IpCandidate {
+    ip: IpAddr,
+    cooldown_until: Option<Instant>,
+}
+
+impl IpCandidate {
+    fn is_healthy(&self) -> bool {
+        self.cooldown_until.map(|until| Instant::now() >= until).unwrap_or(true)
+    }
+}
+
+/// Shape of a DNS-over-HTTPS JSON response (RFC 8484 `application/dns-json`)
+#[derive(Debug, Deserialize)]
+struct DohResponse {
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DohAnswer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DohAnswer {
+    data: String,
+}
+
+/// Response shape of a successful `grant_type=refresh_token` exchange
+#[derive(Debug, Deserialize)]
+struct RefreshTokenResponse {
+    access_token: String,
+    refresh_token: String,
+    expires_in: u64,
+}
+
+/// Build a `reqwest::Client` that resolves [`PIXIV_APP_API_HOST`] straight to `ip`,
+/// bypassing SNI-based blocking
+fn build_client(ip: IpAddr) -> Result<Client> {
+    let socket_addr = std::net::SocketAddr::new(ip, 443);
+    reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .resolve(PIXIV_APP_API_HOST, socket_addr)
+        .build()
+        .map_err(|e| PixivError::NetworkError(NetworkError::RequestError(e)))
+}
+
+/// Whether `err` represents a transport-level connection/TLS failure, as
+/// opposed to an HTTP-level error response — only the former warrants
+/// [`BypassSniClient::rotate_ip`]
+fn is_connection_error(err: &PixivError) -> bool {
+    matches!(err, PixivError::NetworkError(NetworkError::RequestError(e)) if e.is_connect() || e.is_timeout())
+}
 
 /// SNI bypass HTTP client for accessing Pixiv API by bypassing network restrictions
 ///
@@ -20,69 +95,249 @@ use tracing::debug;
 /// ```
 #[derive(Debug, Clone)]
 pub struct BypassSniClient {
-    /// Internal reqwest client
-    pub(crate) client: Client,
-    /// Authentication token
-    access_token: Option<String>,
+    /// Internal reqwest client, rebuilt in place by [`Self::rotate_ip`] whenever
+    /// the active IP is marked dead
+    pub(crate) client: Arc<Mutex<Client>>,
+    /// Candidate IPs this client can rotate through, with per-IP cooldowns
+    ip_pool: Arc<Mutex<Vec<IpCandidate>>>,
+    /// The IP [`Self::client`] currently resolves [`PIXIV_APP_API_HOST`] to
+    active_ip: Arc<Mutex<IpAddr>>,
+    /// Authentication token, shared across clones so [`Self::login_with_refresh_token`]
+    /// updates are visible to every holder of this client
+    access_token: Arc<Mutex<Option<String>>>,
     /// Refresh token
-    refresh_token: Option<String>,
+    refresh_token: Arc<Mutex<Option<String>>>,
+    /// When the current access token expires, set after a successful
+    /// [`Self::login_with_refresh_token`]
+    token_expiry: Arc<Mutex<Option<Instant>>>,
+    /// Salt mixed into the `x-client-hash` security header, overridable via [`Self::set_hash_secret`]
+    hash_secret: Arc<Mutex<String>>,
+    /// Most recently observed rate-limit state
+    rate_limit: Arc<Mutex<Option<RateLimitInfo>>>,
+    /// Whether to transparently sleep and retry on `429` responses
+    auto_retry_rate_limit: bool,
+    /// Maximum number of retries before giving up on a rate-limited request
+    max_rate_limit_retries: u32,
     /// API base URL
     base_url: String,
-    /// IP address for bypass
-    pub ip: IpAddr,
 }
 
 impl BypassSniClient {
-    /// Create new SNI bypass HTTP client instance
+    /// Create new SNI bypass HTTP client instance pinned to a single IP
+    ///
+    /// Equivalent to `with_ips(&[ip])`; see that constructor for pool/failover behavior.
     pub fn new(ip: &str) -> Result<Self> {
-        let ip = ip
-            .parse::<std::net::IpAddr>()
-            .map_err(|_| PixivError::NetworkError(NetworkError::InvalidUrl(format!(
-                "Invalid IP address: {}",
-                ip
-            ))))?;
-
-        tracing::info!(ip = %ip, "Using SNI bypass with IP address");
-
-        // Create SNI bypass client
-        let mut builder = reqwest::Client::builder();
-        // Use port 443 for SNI bypass
-        let socket_addr = std::net::SocketAddr::new(ip, 443);
-        builder = builder
-            .danger_accept_invalid_certs(true)
-            .resolve("app-api.pixiv.net", socket_addr);
-
-        let client = builder
-            .build()
-            .map_err(|e| PixivError::NetworkError(NetworkError::RequestError(e)))?;
+        Self::with_ips(&[ip])
+    }
+
+    /// Create a SNI bypass HTTP client backed by a pool of candidate IPs
+    ///
+    /// The first address is used to resolve [`PIXIV_APP_API_HOST`] immediately.
+    /// If [`Self::send_request`] hits a connection or TLS failure, the
+    /// offending IP is put on a cooldown and the client rotates to the next
+    /// healthy candidate, rebuilding the underlying `reqwest::Client` in
+    /// place — so a dead edge IP doesn't require re-instantiating the client.
+    pub fn with_ips(ips: &[&str]) -> Result<Self> {
+        let candidates = ips
+            .iter()
+            .map(|ip| {
+                ip.parse::<IpAddr>()
+                    .map(|ip| IpCandidate { ip, cooldown_until: None })
+                    .map_err(|_| PixivError::NetworkError(NetworkError::InvalidUrl(format!("Invalid IP address: {}", ip))))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let ip = candidates
+            .first()
+            .ok_or_else(|| PixivError::NetworkError(NetworkError::InvalidUrl("No IP addresses provided".to_string())))?
+            .ip;
+
+        tracing::info!(ip = %ip, pool_size = candidates.len(), "Using SNI bypass with IP pool");
+
+        let client = build_client(ip)?;
 
         Ok(Self {
-            client,
-            access_token: None,
-            refresh_token: None,
+            client: Arc::new(Mutex::new(client)),
+            ip_pool: Arc::new(Mutex::new(candidates)),
+            active_ip: Arc::new(Mutex::new(ip)),
+            access_token: Arc::new(Mutex::new(None)),
+            refresh_token: Arc::new(Mutex::new(None)),
+            token_expiry: Arc::new(Mutex::new(None)),
+            hash_secret: Arc::new(Mutex::new(DEFAULT_HASH_SECRET.to_string())),
+            rate_limit: Arc::new(Mutex::new(None)),
+            auto_retry_rate_limit: true,
+            max_rate_limit_retries: DEFAULT_MAX_RATE_LIMIT_RETRIES,
             base_url: format!("https://{}", ip),
-            ip,
         })
     }
 
+    /// The IP currently used to resolve [`PIXIV_APP_API_HOST`]
+    pub fn ip(&self) -> IpAddr {
+        *self.active_ip.lock().unwrap()
+    }
+
+    /// Mark the currently active IP dead for [`IP_COOLDOWN`], then rebuild the
+    /// client against the next healthy candidate in the pool
+    ///
+    /// Errs if every candidate (including the one just marked dead) is still
+    /// cooling down.
+    fn rotate_ip(&self) -> Result<()> {
+        let current = self.ip();
+        let mut pool = self.ip_pool.lock().unwrap();
+
+        if let Some(candidate) = pool.iter_mut().find(|c| c.ip == current) {
+            candidate.cooldown_until = Some(Instant::now() + IP_COOLDOWN);
+        }
+
+        let next = pool
+            .iter()
+            .find(|c| c.ip != current && c.is_healthy())
+            .or_else(|| pool.iter().find(|c| c.ip != current))
+            .map(|c| c.ip)
+            .ok_or_else(|| PixivError::NetworkError(NetworkError::InvalidUrl("No healthy bypass IP available in pool".to_string())))?;
+        drop(pool);
+
+        warn!(from = %current, to = %next, "Rotating SNI bypass IP after connection failure");
+        let rebuilt = build_client(next)?;
+        *self.client.lock().unwrap() = rebuilt;
+        *self.active_ip.lock().unwrap() = next;
+
+        Ok(())
+    }
+
+    /// Query a DNS-over-HTTPS resolver for fresh addresses of `hostname` and
+    /// add any not already in the pool
+    ///
+    /// Lets a long-running client self-heal in restricted networks where
+    /// the originally pinned IPs have since been blocked, without the
+    /// caller having to hardcode a replacement list. Returns the number of
+    /// newly added candidates.
+    pub async fn refresh_ips_from_doh(&self, hostname: &str) -> Result<usize> {
+        let doh_client = reqwest::Client::new();
+        let response = doh_client
+            .get(DOH_RESOLVER_URL)
+            .query(&[("name", hostname), ("type", "A")])
+            .header("accept", "application/dns-json")
+            .send()
+            .await?;
+
+        let parsed: DohResponse = response.json().await?;
+        let discovered: Vec<IpAddr> = parsed.answer.iter().filter_map(|a| a.data.parse().ok()).collect();
+
+        let mut pool = self.ip_pool.lock().unwrap();
+        let mut added = 0;
+        for ip in discovered {
+            if !pool.iter().any(|c| c.ip == ip) {
+                pool.push(IpCandidate { ip, cooldown_until: None });
+                added += 1;
+            }
+        }
+
+        debug!(hostname = %hostname, added = added, "Refreshed SNI bypass IP pool from DoH");
+        Ok(added)
+    }
+
+    /// Toggle automatic sleep-and-retry behavior when a request is rate-limited (`429`)
+    ///
+    /// Enabled by default. Disable it for callers that want to handle `429`s themselves.
+    pub fn with_auto_retry_rate_limit(mut self, enabled: bool) -> Self {
+        self.auto_retry_rate_limit = enabled;
+        self
+    }
+
+    /// Override the maximum number of retries for a rate-limited request
+    pub fn with_max_rate_limit_retries(mut self, retries: u32) -> Self {
+        self.max_rate_limit_retries = retries;
+        self
+    }
+
+    /// The most recently observed rate-limit state, if any response has included one
+    pub fn rate_limit(&self) -> Option<RateLimitInfo> {
+        self.rate_limit.lock().unwrap().clone()
+    }
+
     /// Set authentication token
-    pub fn set_access_token(&mut self, token: String) {
-        self.access_token = Some(token);
+    ///
+    /// Takes `&self` since the token is stored behind a shared lock, letting
+    /// [`Self::login_with_refresh_token`] update it from within
+    /// [`Self::send_request`] without needing exclusive access to the client.
+    pub fn set_access_token(&self, token: String) {
+        *self.access_token.lock().unwrap() = Some(token);
     }
 
     /// Get current authentication token
-    pub fn access_token(&self) -> Option<&str> {
-        self.access_token.as_deref()
+    pub fn access_token(&self) -> Option<String> {
+        self.access_token.lock().unwrap().clone()
     }
 
     /// Set refresh token
-    pub fn set_refresh_token(&mut self, token: String) {
-        self.refresh_token = Some(token);
+    pub fn set_refresh_token(&self, token: String) {
+        *self.refresh_token.lock().unwrap() = Some(token);
     }
 
     /// Get current refresh token
-    pub fn refresh_token(&self) -> Option<&str> {
-        self.refresh_token.as_deref()
+    pub fn refresh_token(&self) -> Option<String> {
+        self.refresh_token.lock().unwrap().clone()
+    }
+
+    /// Override the salt mixed into the `x-client-hash` security header
+    ///
+    /// Lets the signing salt be rotated at runtime (Pixiv has changed it
+    /// before) without recompiling against a new hardcoded constant.
+    pub fn set_hash_secret(&self, secret: String) {
+        *self.hash_secret.lock().unwrap() = secret;
+    }
+
+    /// Exchange the stored refresh token for a new access token
+    ///
+    /// POSTs to Pixiv's OAuth token endpoint with `grant_type=refresh_token`
+    /// and the `x-client-time`/`x-client-hash` headers from
+    /// [`Self::generate_security_headers`], then stores the renewed
+    /// access/refresh tokens and their expiry.
+    pub async fn login_with_refresh_token(&self) -> Result<()> {
+        let refresh_token = self
+            .refresh_token
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| PixivError::AuthError("No refresh token available".to_string()))?;
+
+        debug!("Refreshing SNI bypass access token");
+
+        let mut form = HashMap::new();
+        form.insert("client_id", CLIENT_ID);
+        form.insert("client_secret", CLIENT_SECRET);
+        form.insert("grant_type", "refresh_token");
+        form.insert("refresh_token", refresh_token.as_str());
+        form.insert("get_secure_url", "true");
+
+        let mut request = self.client.lock().unwrap().post(AUTH_URL);
+        for (key, value) in self.generate_security_headers() {
+            request = request.header(&key, value);
+        }
+        request = request.form(&form);
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_else(|_| "Failed to get error information".to_string());
+            return Err(PixivError::AuthError(format!("Token refresh failed: {}", error_text)));
+        }
+
+        let token: RefreshTokenResponse = response.json().await?;
+        *self.access_token.lock().unwrap() = Some(token.access_token);
+        *self.refresh_token.lock().unwrap() = Some(token.refresh_token);
+        *self.token_expiry.lock().unwrap() = Some(Instant::now() + Duration::from_secs(token.expires_in));
+
+        debug!("SNI bypass access token refreshed");
+        Ok(())
+    }
+
+    /// Whether the current token is missing its expiry or within [`EXPIRY_SKEW`] of it
+    fn token_needs_refresh(&self) -> bool {
+        match *self.token_expiry.lock().unwrap() {
+            Some(expiry) => Instant::now() + EXPIRY_SKEW >= expiry,
+            None => false,
+        }
     }
 
     /// Send GET request
@@ -96,25 +351,142 @@ impl BypassSniClient {
     }
 
     /// Send authenticated API request
+    ///
+    /// If the stored token is expired (or close to it) and a refresh token
+    /// is available, proactively renews it via
+    /// [`Self::login_with_refresh_token`] before sending. If the request
+    /// comes back `401 Unauthorized`, renews once more and retries. If it
+    /// comes back `429 Too Many Requests` and [`Self::with_auto_retry_rate_limit`]
+    /// is enabled (the default), sleeps until the parsed reset time (or an
+    /// exponential backoff, if the response carried no usable rate-limit
+    /// headers) and retries, up to [`Self::with_max_rate_limit_retries`] times.
     pub async fn send_request<T: Serialize + ?Sized>(
         &self,
         method: reqwest::Method,
         url: &str,
         body: Option<&T>,
+    ) -> Result<Response> {
+        if self.token_needs_refresh() && self.refresh_token.lock().unwrap().is_some() {
+            self.login_with_refresh_token().await?;
+        }
+
+        let mut attempt = 0u32;
+        let mut reauthed = false;
+        let mut ip_rotations = 0u32;
+        let max_ip_rotations = self.ip_pool.lock().unwrap().len() as u32;
+
+        loop {
+            let response = match self.send_request_once(method.clone(), url, body).await {
+                Ok(response) => response,
+                Err(e) if is_connection_error(&e) && ip_rotations < max_ip_rotations => {
+                    warn!(error = %e, "SNI bypass request failed to connect, rotating IP");
+                    self.rotate_ip()?;
+                    ip_rotations += 1;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            let status = response.status();
+
+            if let Some(info) = RateLimitInfo::from_headers(response.headers()) {
+                *self.rate_limit.lock().unwrap() = Some(info);
+            }
+
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                let wait = self.rate_limit_backoff(response.headers(), attempt);
+
+                if self.auto_retry_rate_limit && attempt < self.max_rate_limit_retries {
+                    warn!(attempt = attempt, wait_secs = wait.as_secs_f64(), "Rate limited, backing off before retry");
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                    continue;
+                }
+
+                if !self.auto_retry_rate_limit {
+                    return Err(PixivError::RateLimited { retry_after: wait });
+                }
+
+                let error_text = response.text().await.unwrap_or_else(|_| "Failed to get error information".to_string());
+                return Err(PixivError::ApiError(format!(
+                    "Rate limited after {} attempts: {}",
+                    attempt + 1,
+                    error_text
+                )));
+            }
+
+            if status == reqwest::StatusCode::UNAUTHORIZED && !reauthed && self.refresh_token.lock().unwrap().is_some() {
+                warn!("Got 401, refreshing SNI bypass token and retrying once");
+                reauthed = true;
+                self.login_with_refresh_token().await?;
+                continue;
+            }
+
+            if !status.is_success() {
+                return Err(PixivError::ApiError(format!(
+                    "API request failed: {} - {}",
+                    status,
+                    response.text().await.unwrap_or_else(|_| "Failed to get error information".to_string())
+                )));
+            }
+
+            return Ok(response);
+        }
+    }
+
+    /// Compute how long to sleep before retrying a rate-limited request
+    ///
+    /// Prefers the server-provided `Retry-After` header, falls back to the
+    /// parsed reset timestamp, and finally to exponential backoff, always
+    /// adding a small jitter to avoid a thundering herd of retries.
+    fn rate_limit_backoff(&self, headers: &reqwest::header::HeaderMap, attempt: u32) -> Duration {
+        let jitter = Duration::from_millis((rand::random::<f64>() * 250.0) as u64);
+
+        if let Some(retry_after) = headers.get(reqwest::header::RETRY_AFTER).and_then(|value| value.to_str().ok()) {
+            if let Ok(secs) = retry_after.parse::<u64>() {
+                return Duration::from_secs(secs) + jitter;
+            }
+            if let Ok(at) = chrono::DateTime::parse_from_rfc2822(retry_after) {
+                let secs = (at.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_seconds().max(0) as u64;
+                return Duration::from_secs(secs) + jitter;
+            }
+        }
+
+        if let Some(reset_at) = RateLimitInfo::from_headers(headers).and_then(|info| info.reset_at) {
+            let secs = (reset_at - chrono::Utc::now()).num_seconds().max(0) as u64;
+            return Duration::from_secs(secs) + jitter;
+        }
+
+        Duration::from_millis(500 * 2u64.saturating_pow(attempt)) + jitter
+    }
+
+    async fn send_request_once<T: Serialize + ?Sized>(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        body: Option<&T>,
     ) -> Result<Response> {
         debug!(method = %method, url = %url, "Sending API request with SNI bypass");
 
-        // Set Host header
+        // Set Host header, plus the x-client-time/x-client-hash pair Pixiv's
+        // app API validates on every request
         let mut headers = reqwest::header::HeaderMap::new();
         headers.insert(
             reqwest::header::HOST,
             reqwest::header::HeaderValue::from_static("app-api.pixiv.net"),
         );
+        for (key, value) in self.generate_security_headers() {
+            if let (Ok(name), Ok(value)) = (
+                reqwest::header::HeaderName::from_bytes(key.as_bytes()),
+                reqwest::header::HeaderValue::from_str(&value),
+            ) {
+                headers.insert(name, value);
+            }
+        }
 
-        let mut request = self.client.request(method.clone(), url).headers(headers);
+        let mut request = self.client.lock().unwrap().request(method.clone(), url).headers(headers);
 
         // Add authentication header
-        if let Some(token) = &self.access_token {
+        if let Some(token) = self.access_token() {
             request = request.header("Authorization", format!("Bearer {}", token));
         }
 
@@ -123,19 +495,8 @@ impl BypassSniClient {
             request = request.json(body);
         }
 
-        // Send request
         let response = request.send().await?;
-
-        // Check response status
-        if !response.status().is_success() {
-            return Err(PixivError::ApiError(format!(
-                "API request failed: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_else(|_| "Failed to get error information".to_string())
-            )));
-        }
-
-        debug!(status = %response.status(), "API request completed successfully");
+        debug!(status = %response.status(), "API request completed");
         Ok(response)
     }
 
@@ -158,7 +519,7 @@ impl BypassSniClient {
 
         // Generate x-client-time and x-client-hash
         let local_time = Utc::now().format("%Y-%m-%dT%H:%M:%S+00:00").to_string();
-        let hash_input = format!("{}{}", local_time, "28c1fdd170a5204386cb1313c7077b34f83e4aaf4aa829ce78c231e05b0bae2c");
+        let hash_input = format!("{}{}", local_time, self.hash_secret.lock().unwrap());
         let hash = format!("{:x}", compute(hash_input));
 
         headers.insert("x-client-time".to_string(), local_time);
@@ -183,4 +544,105 @@ mod tests {
         let result = BypassSniClient::new("invalid_ip");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_token_accessors_round_trip_without_mut() {
+        let client = BypassSniClient::new("210.140.131.145").unwrap();
+        assert_eq!(client.access_token(), None);
+
+        client.set_access_token("token".to_string());
+        client.set_refresh_token("refresh".to_string());
+        assert_eq!(client.access_token(), Some("token".to_string()));
+        assert_eq!(client.refresh_token(), Some("refresh".to_string()));
+    }
+
+    #[test]
+    fn test_token_needs_refresh_without_expiry_is_false() {
+        let client = BypassSniClient::new("210.140.131.145").unwrap();
+        assert!(!client.token_needs_refresh());
+    }
+
+    #[test]
+    fn test_token_needs_refresh_past_expiry_is_true() {
+        let client = BypassSniClient::new("210.140.131.145").unwrap();
+        *client.token_expiry.lock().unwrap() = Some(Instant::now() - Duration::from_secs(1));
+        assert!(client.token_needs_refresh());
+    }
+
+    #[test]
+    fn test_set_hash_secret_changes_generated_hash() {
+        let client = BypassSniClient::new("210.140.131.145").unwrap();
+        let before = client.generate_security_headers();
+
+        client.set_hash_secret("a-different-salt".to_string());
+        let after = client.generate_security_headers();
+
+        assert_ne!(before["x-client-hash"], after["x-client-hash"]);
+    }
+
+    #[test]
+    fn test_rate_limit_defaults_to_none_and_auto_retry_enabled() {
+        let client = BypassSniClient::new("210.140.131.145").unwrap();
+        assert_eq!(client.rate_limit(), None);
+        assert!(client.auto_retry_rate_limit);
+        assert_eq!(client.max_rate_limit_retries, DEFAULT_MAX_RATE_LIMIT_RETRIES);
+    }
+
+    #[test]
+    fn test_with_max_rate_limit_retries_overrides_default() {
+        let client = BypassSniClient::new("210.140.131.145").unwrap().with_max_rate_limit_retries(7);
+        assert_eq!(client.max_rate_limit_retries, 7);
+    }
+
+    #[test]
+    fn test_rate_limit_backoff_without_headers_is_exponential() {
+        let client = BypassSniClient::new("210.140.131.145").unwrap();
+        let headers = reqwest::header::HeaderMap::new();
+
+        let first = client.rate_limit_backoff(&headers, 0);
+        let second = client.rate_limit_backoff(&headers, 1);
+
+        assert!(first.as_millis() >= 500 && first.as_millis() < 750);
+        assert!(second.as_millis() >= 1000 && second.as_millis() < 1250);
+    }
+
+    #[test]
+    fn test_rate_limit_backoff_honors_retry_after_seconds() {
+        let client = BypassSniClient::new("210.140.131.145").unwrap();
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, "5".parse().unwrap());
+
+        let wait = client.rate_limit_backoff(&headers, 0);
+        assert!(wait.as_millis() >= 5000 && wait.as_millis() < 5250);
+    }
+
+    #[test]
+    fn test_with_ips_starts_on_first_address() {
+        let client = BypassSniClient::with_ips(&["210.140.131.145", "210.140.131.199"]).unwrap();
+        assert_eq!(client.ip(), "210.140.131.145".parse::<IpAddr>().unwrap());
+    }
+
+    #[test]
+    fn test_with_ips_rejects_empty_pool() {
+        let result = BypassSniClient::with_ips(&[]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_rotate_ip_switches_to_next_healthy_candidate() {
+        let client = BypassSniClient::with_ips(&["210.140.131.145", "210.140.131.199"]).unwrap();
+        client.rotate_ip().unwrap();
+        assert_eq!(client.ip(), "210.140.131.199".parse::<IpAddr>().unwrap());
+    }
+
+    #[test]
+    fn test_rotate_ip_fails_with_single_candidate_on_cooldown() {
+        let client = BypassSniClient::with_ips(&["210.140.131.145"]).unwrap();
+        assert!(client.rotate_ip().is_err());
+    }
+
+    #[test]
+    fn test_is_connection_error_distinguishes_transport_from_api_errors() {
+        assert!(!is_connection_error(&PixivError::ApiError("boom".to_string())));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,72 @@
+//! In-memory TTL cache for GET responses, keyed by request URL
+//!
+//! Avoids re-hitting Pixiv for repeated lookups of the same resource (e.g. an
+//! illustration or user detail) within a configurable time window, which also
+//! helps stay under the rate limits [`crate::error::PixivError`] models.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// A single cached response, paired with when it was stored
+type Entry = (Instant, serde_json::Value);
+
+/// Holds cached GET responses plus optional per-endpoint TTL overrides
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    entries: HashMap<String, Entry>,
+    default_ttl: Duration,
+    /// `(url substring, ttl)` pairs checked in insertion order; the first match wins
+    endpoint_ttls: Vec<(String, Duration)>,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(default_ttl: Duration) -> Self {
+        Self { entries: HashMap::new(), default_ttl, endpoint_ttls: Vec::new() }
+    }
+
+    pub(crate) fn set_endpoint_ttl(&mut self, path_prefix: String, ttl: Duration) {
+        self.endpoint_ttls.push((path_prefix, ttl));
+    }
+
+    fn ttl_for(&self, url: &str) -> Duration {
+        self.endpoint_ttls
+            .iter()
+            .find(|(substring, _)| url.contains(substring.as_str()))
+            .map(|(_, ttl)| *ttl)
+            .unwrap_or(self.default_ttl)
+    }
+
+    /// The cached value for `url`, if present and not yet past its TTL
+    pub(crate) fn get(&self, url: &str) -> Option<serde_json::Value> {
+        let (stored_at, value) = self.entries.get(url)?;
+        (stored_at.elapsed() < self.ttl_for(url)).then(|| value.clone())
+    }
+
+    pub(crate) fn put(&mut self, url: String, value: serde_json::Value) {
+        self.entries.insert(url, (Instant::now(), value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_expires_after_ttl() {
+        let mut cache = ResponseCache::new(Duration::from_millis(10));
+        cache.put("https://example.com/a".to_string(), serde_json::json!({"a": 1}));
+
+        assert!(cache.get("https://example.com/a").is_some());
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(cache.get("https://example.com/a").is_none());
+    }
+
+    #[test]
+    fn test_endpoint_ttl_overrides_default() {
+        let mut cache = ResponseCache::new(Duration::from_secs(0));
+        cache.set_endpoint_ttl("/v1/user/detail".to_string(), Duration::from_secs(60));
+        cache.put("https://example.com/v1/user/detail?user_id=1".to_string(), serde_json::json!({"id": 1}));
+
+        assert!(cache.get("https://example.com/v1/user/detail?user_id=1").is_some());
+    }
+}
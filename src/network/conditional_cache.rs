@@ -0,0 +1,133 @@
+//! Conditional-request (`ETag`/`Last-Modified`) cache for GET responses
+//!
+//! Unlike [`super::cache::ResponseCache`], which serves a stale-free value
+//! unconditionally until its TTL lapses, this cache always revalidates with
+//! the server via `If-None-Match`/`If-Modified-Since` and only skips
+//! re-fetching the body on a `304 Not Modified` — useful for endpoints like
+//! `user_detail` that change rarely but are polled often.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Tunables for a [`ConditionalCacheStore`]
+///
+/// `max_entries` bounds memory use by evicting the oldest entry once full;
+/// `ttl` is a safety net that forces revalidation from scratch (dropping the
+/// stored `ETag`/`Last-Modified`) even if the server never stops returning `304`.
+#[derive(Debug, Clone, Copy)]
+pub struct CacheConfig {
+    /// Maximum number of cached entries before the oldest is evicted
+    pub max_entries: usize,
+    /// How long a stored entry may be revalidated before it is dropped outright
+    pub ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self { max_entries: 256, ttl: Duration::from_secs(3600) }
+    }
+}
+
+/// A previously fetched response, kept so it can be returned again on `304 Not Modified`
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    /// `ETag` from the stored response, sent back as `If-None-Match`
+    pub etag: Option<String>,
+    /// `Last-Modified` from the stored response, sent back as `If-Modified-Since`
+    pub last_modified: Option<String>,
+    /// The deserialized JSON body
+    pub value: serde_json::Value,
+    stored_at: Instant,
+}
+
+/// Pluggable backend for the conditional-request cache
+///
+/// The default [`InMemoryConditionalCache`] is sufficient for a single
+/// process; implement this trait to share entries across processes (e.g. Redis).
+pub trait ConditionalCacheStore: std::fmt::Debug + Send + Sync {
+    /// The entry stored for `key`, if any and not yet past [`CacheConfig::ttl`]
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+    /// Store (or replace) the entry for `key`
+    fn put(&self, key: String, entry: CachedResponse);
+    /// Number of entries currently held
+    fn len(&self) -> usize;
+}
+
+/// Default in-process [`ConditionalCacheStore`], bounded by [`CacheConfig::max_entries`]
+#[derive(Debug)]
+pub struct InMemoryConditionalCache {
+    config: CacheConfig,
+    entries: Mutex<HashMap<String, CachedResponse>>,
+}
+
+impl InMemoryConditionalCache {
+    /// Create an empty store with the given config
+    pub fn new(config: CacheConfig) -> Arc<Self> {
+        Arc::new(Self { config, entries: Mutex::new(HashMap::new()) })
+    }
+}
+
+impl ConditionalCacheStore for InMemoryConditionalCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get(key)?;
+        if entry.stored_at.elapsed() >= self.config.ttl {
+            entries.remove(key);
+            return None;
+        }
+        entries.get(key).cloned()
+    }
+
+    fn put(&self, key: String, mut entry: CachedResponse) {
+        entry.stored_at = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.config.max_entries && !entries.contains_key(&key) {
+            if let Some(oldest) = entries.iter().min_by_key(|(_, v)| v.stored_at).map(|(k, _)| k.clone()) {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, entry);
+    }
+
+    fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+}
+
+impl CachedResponse {
+    /// Build a fresh entry as it would be stored right after a `200 OK`
+    pub fn new(etag: Option<String>, last_modified: Option<String>, value: serde_json::Value) -> Self {
+        Self { etag, last_modified, value, stored_at: Instant::now() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_expires_after_ttl() {
+        let store = InMemoryConditionalCache::new(CacheConfig { max_entries: 10, ttl: Duration::from_millis(10) });
+        store.put("a".to_string(), CachedResponse::new(Some("\"v1\"".to_string()), None, serde_json::json!({"a": 1})));
+
+        assert!(store.get("a").is_some());
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(store.get("a").is_none());
+    }
+
+    #[test]
+    fn test_evicts_oldest_entry_once_full() {
+        let store = InMemoryConditionalCache::new(CacheConfig { max_entries: 2, ttl: Duration::from_secs(60) });
+        store.put("a".to_string(), CachedResponse::new(None, None, serde_json::json!(1)));
+        std::thread::sleep(Duration::from_millis(5));
+        store.put("b".to_string(), CachedResponse::new(None, None, serde_json::json!(2)));
+        std::thread::sleep(Duration::from_millis(5));
+        store.put("c".to_string(), CachedResponse::new(None, None, serde_json::json!(3)));
+
+        assert_eq!(store.len(), 2);
+        assert!(store.get("a").is_none());
+        assert!(store.get("b").is_some());
+        assert!(store.get("c").is_some());
+    }
+}
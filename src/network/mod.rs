@@ -1,105 +1,831 @@
+mod access_log;
+mod auth_backend;
 pub mod bypass_sni;
+mod cache;
+mod conditional_cache;
+mod rate_limit;
+mod reauth;
+mod response;
 
-use crate::error::{NetworkError, PixivError, Result};
-use reqwest::{Client, Response};
+use cache::ResponseCache;
+
+pub use access_log::{AccessLogEntry, AccessLogSink, CallbackAccessLogSink, FileAccessLogSink};
+pub use auth_backend::{AuthBackend, RefreshTokenAuth, StaticTokenAuth};
+pub use conditional_cache::{CacheConfig, CachedResponse, ConditionalCacheStore, InMemoryConditionalCache};
+pub use rate_limit::RateLimitInfo;
+pub use reauth::ReauthHandler;
+pub use response::HttpResponse;
+
+use crate::error::{ApiErrorCode, ApiErrorDetails, NetworkError, PixivError, Result};
+use reqwest::Client;
 use serde::Serialize;
 use std::collections::HashMap;
-use tracing::debug;
+use std::sync::{Arc, Mutex};
+use std::time::Duration as StdDuration;
+use tracing::{debug, warn};
+
+/// Default number of retries for a rate-limited request before giving up
+const DEFAULT_MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Default starting delay for computed (non-`Retry-After`) backoff
+const DEFAULT_RETRY_BASE_DELAY: StdDuration = StdDuration::from_millis(500);
+
+/// Default ceiling on computed backoff, regardless of attempt count
+const DEFAULT_RETRY_MAX_DELAY: StdDuration = StdDuration::from_secs(30);
+
+/// Tunable policy for retrying rate-limited (`429`/`503`) or transport-level
+/// (timeout/connection) request failures
+///
+/// The computed backoff for a given `attempt` (0-based) is
+/// `min(max_delay, base_delay * 2^attempt)` plus random jitter in
+/// `[0, delay/2]`. A server-provided `Retry-After` header always takes
+/// precedence over the computed value. Disable retries entirely by setting
+/// `enabled` to `false`.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Whether to transparently sleep and retry on 429/503 responses or transport errors
+    pub enabled: bool,
+    /// Starting delay for the exponential backoff computation
+    pub base_delay: StdDuration,
+    /// Upper bound on the computed backoff delay (before jitter)
+    pub max_delay: StdDuration,
+    /// Maximum number of retries before giving up
+    pub max_retries: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            base_delay: DEFAULT_RETRY_BASE_DELAY,
+            max_delay: DEFAULT_RETRY_MAX_DELAY,
+            max_retries: DEFAULT_MAX_RATE_LIMIT_RETRIES,
+        }
+    }
+}
+
+/// Which compressed transfer encodings to advertise via `Accept-Encoding`
+/// when compression negotiation is enabled
+///
+/// All three are advertised by default; disable individual codecs to match
+/// a picky intermediary, or to avoid paying the decode cost of one of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionEncodings {
+    /// Advertise and accept `gzip`
+    pub gzip: bool,
+    /// Advertise and accept `deflate`
+    pub deflate: bool,
+    /// Advertise and accept Brotli (`br`)
+    pub br: bool,
+}
+
+impl CompressionEncodings {
+    /// All supported encodings enabled
+    pub const ALL: Self = Self { gzip: true, deflate: true, br: true };
+    /// No encodings enabled; compression negotiation sends no `Accept-Encoding`
+    pub const NONE: Self = Self { gzip: false, deflate: false, br: false };
+
+    /// Render the `Accept-Encoding` header value for the enabled codecs, if any
+    fn accept_encoding_header(&self) -> Option<String> {
+        let mut values = Vec::new();
+        if self.gzip {
+            values.push("gzip");
+        }
+        if self.deflate {
+            values.push("deflate");
+        }
+        if self.br {
+            values.push("br");
+        }
+        if values.is_empty() {
+            None
+        } else {
+            Some(values.join(", "))
+        }
+    }
+}
+
+impl Default for CompressionEncodings {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
 
 /// HTTP client for communicating with Pixiv API
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct HttpClient {
     /// Internal reqwest client
     pub client: Client,
-    /// Authentication token
-    access_token: Option<String>,
+    /// Authentication token, shared across clones so a transparent refresh
+    /// is visible to every holder of this client
+    access_token: Arc<Mutex<Option<String>>>,
     /// Refresh token
-    refresh_token: Option<String>,
+    refresh_token: Arc<Mutex<Option<String>>>,
     /// API base URL
     base_url: String,
+    /// Whether to negotiate compressed transfer via `Accept-Encoding`
+    accept_compression: bool,
+    /// Which encodings to advertise when compression negotiation is enabled
+    compression_encodings: CompressionEncodings,
+    /// Most recently observed rate-limit state, shared across clones
+    rate_limit: Arc<Mutex<Option<RateLimitInfo>>>,
+    /// Retry behavior for rate-limited responses and transport errors
+    retry_policy: RetryPolicy,
+    /// Optional hook invoked to recover from an expired access token
+    reauth: Option<Arc<dyn ReauthHandler>>,
+    /// If set, proactively refresh the token when it is within this long of expiring
+    proactive_reauth_skew: Option<StdDuration>,
+    /// Optional TTL cache for [`Self::get_cached`], shared across clones
+    cache: Option<Arc<Mutex<ResponseCache>>>,
+    /// Optional `ETag`/`Last-Modified` revalidation cache for [`Self::get_conditional`]
+    conditional_cache: Option<Arc<dyn ConditionalCacheStore>>,
+    /// Optional structured access-log sink, recorded once per [`Self::send_request`] call
+    access_log: Option<Arc<dyn AccessLogSink>>,
+    /// Optional pluggable token source, preferred over [`Self::access_token`]/[`Self::reauth`] when set
+    auth_backend: Option<Arc<dyn AuthBackend>>,
+}
+
+impl std::fmt::Debug for HttpClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpClient")
+            .field("client", &self.client)
+            .field("base_url", &self.base_url)
+            .field("accept_compression", &self.accept_compression)
+            .field("compression_encodings", &self.compression_encodings)
+            .field("retry_policy", &self.retry_policy)
+            .field("reauth", &self.reauth.is_some())
+            .field("proactive_reauth_skew", &self.proactive_reauth_skew)
+            .field("cache_enabled", &self.cache.is_some())
+            .field("conditional_cache_enabled", &self.conditional_cache.is_some())
+            .field("access_log_enabled", &self.access_log.is_some())
+            .field("auth_backend_enabled", &self.auth_backend.is_some())
+            .finish()
+    }
+}
+
+/// Builder for [`HttpClient`], for transport options that must be set before
+/// the underlying `reqwest::Client` is built (a request timeout, or a
+/// non-default base URL)
+///
+/// TLS backend is chosen at compile time via Cargo features (`default-tls`
+/// by default, or `rustls-tls-webpki-roots`/`rustls-tls-native-roots`),
+/// which map directly to reqwest's own features of the same name — useful
+/// for musl/static builds or rustls-only environments. No runtime
+/// configuration is needed for that part.
+pub struct HttpClientBuilder {
+    base_url: String,
+    timeout: Option<StdDuration>,
+    retry_policy: RetryPolicy,
+}
+
+impl HttpClientBuilder {
+    /// Start a builder with the default Pixiv app API base URL and no timeout
+    pub fn new() -> Self {
+        Self {
+            base_url: "https://app-api.pixiv.net".to_string(),
+            timeout: None,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Override the API base URL (e.g. for a test server or mirror)
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Bound the latency of any single request
+    ///
+    /// A connection that hangs past `timeout` surfaces as a
+    /// [`PixivError::NetworkError`] instead of hanging forever.
+    pub fn timeout(mut self, timeout: StdDuration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Toggle automatic sleep-and-retry behavior when a request is rate-limited (`429`/`503`)
+    /// or fails with a transport-level timeout/connection error
+    ///
+    /// Enabled by default. See [`HttpClient::with_auto_retry_rate_limit`] for
+    /// the post-construction equivalent.
+    pub fn auto_retry_rate_limit(mut self, enabled: bool) -> Self {
+        self.retry_policy.enabled = enabled;
+        self
+    }
+
+    /// Set the maximum number of retries for a rate-limited or transport-error request
+    pub fn max_rate_limit_retries(mut self, retries: u32) -> Self {
+        self.retry_policy.max_retries = retries;
+        self
+    }
+
+    /// Replace the whole [`RetryPolicy`] wholesale, overriding any prior
+    /// [`auto_retry_rate_limit`](Self::auto_retry_rate_limit) /
+    /// [`max_rate_limit_retries`](Self::max_rate_limit_retries) calls
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Build the [`HttpClient`]
+    pub fn build(self) -> Result<HttpClient> {
+        let mut builder = Client::builder().user_agent("PixivRustClient/0.1.0");
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        let client = builder.build().map_err(NetworkError::RequestError)?;
+
+        Ok(HttpClient {
+            client,
+            access_token: Arc::new(Mutex::new(None)),
+            refresh_token: Arc::new(Mutex::new(None)),
+            base_url: self.base_url,
+            accept_compression: false,
+            compression_encodings: CompressionEncodings::default(),
+            rate_limit: Arc::new(Mutex::new(None)),
+            retry_policy: self.retry_policy,
+            reauth: None,
+            proactive_reauth_skew: None,
+            cache: None,
+            conditional_cache: None,
+            access_log: None,
+            auth_backend: None,
+        })
+    }
+}
+
+impl Default for HttpClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl HttpClient {
     /// Create new HTTP client instance
     pub fn new() -> Result<Self> {
-        let client = Client::builder()
-            .user_agent("PixivRustClient/0.1.0")
-            .build()
-            .map_err(NetworkError::RequestError)?;
+        HttpClientBuilder::new().build()
+    }
 
-        Ok(Self {
-            client,
-            access_token: None,
-            refresh_token: None,
-            base_url: "https://app-api.pixiv.net".to_string(),
-        })
+    /// Start building a client with transport options (timeout, base URL)
+    /// that must be set before the underlying `reqwest::Client` exists
+    pub fn builder() -> HttpClientBuilder {
+        HttpClientBuilder::new()
+    }
+
+    /// Attach a handler that can transparently refresh an expired access token
+    ///
+    /// When set, a `401` response triggers exactly one refresh-and-retry of
+    /// the original request. See [`with_proactive_reauth_skew`](Self::with_proactive_reauth_skew)
+    /// to refresh ahead of expiry instead of reacting to a `401`.
+    pub fn with_reauth_handler(mut self, handler: Arc<dyn ReauthHandler>) -> Self {
+        self.reauth = Some(handler);
+        self
+    }
+
+    /// Proactively refresh the token when it is within `skew` of expiring
+    ///
+    /// Requires a [`ReauthHandler`] to also be set via [`with_reauth_handler`](Self::with_reauth_handler).
+    pub fn with_proactive_reauth_skew(mut self, skew: StdDuration) -> Self {
+        self.proactive_reauth_skew = Some(skew);
+        self
+    }
+
+    /// Source outgoing tokens from a pluggable [`AuthBackend`] instead of [`Self::set_access_token`]
+    ///
+    /// Takes priority over [`Self::access_token`] and [`Self::with_reauth_handler`]
+    /// for both attaching a token and recovering from a `401`. Use
+    /// [`StaticTokenAuth`] to wrap a manually-managed token, or
+    /// [`RefreshTokenAuth`] to adapt an existing [`ReauthHandler`] (e.g.
+    /// `AuthClient`) so callers can swap in a token source of their own
+    /// (a shared cache, an external refresh service) without forking the client.
+    pub fn with_auth_backend(mut self, backend: Arc<dyn AuthBackend>) -> Self {
+        self.auth_backend = Some(backend);
+        self
+    }
+
+    /// Whether a pluggable [`AuthBackend`] is configured
+    pub fn auth_backend_enabled(&self) -> bool {
+        self.auth_backend.is_some()
+    }
+
+    /// Toggle automatic sleep-and-retry behavior when a request is rate-limited
+    /// or fails with a transport-level timeout/connection error
+    ///
+    /// Enabled by default. Disable it for callers that want to handle `429`s themselves.
+    pub fn with_auto_retry_rate_limit(mut self, enabled: bool) -> Self {
+        self.retry_policy.enabled = enabled;
+        self
+    }
+
+    /// Set the maximum number of retries for a rate-limited or transport-error request
+    pub fn with_max_rate_limit_retries(mut self, retries: u32) -> Self {
+        self.retry_policy.max_retries = retries;
+        self
+    }
+
+    /// Replace the whole [`RetryPolicy`] (enablement, base/max delay, retry count) at once
+    ///
+    /// Prefer [`with_auto_retry_rate_limit`](Self::with_auto_retry_rate_limit) /
+    /// [`with_max_rate_limit_retries`](Self::with_max_rate_limit_retries) for
+    /// tweaking a single knob; use this to also tune `base_delay`/`max_delay`.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// The retry policy currently in effect
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    /// The rate-limit state observed on the most recent response, if any
+    pub fn rate_limit(&self) -> Option<RateLimitInfo> {
+        self.rate_limit.lock().unwrap().clone()
+    }
+
+    /// Opt into negotiating compressed transfer (`Accept-Encoding: gzip, deflate, br`
+    /// by default; narrow it with [`with_compression_encodings`](Self::with_compression_encodings))
+    ///
+    /// Response bodies are transparently decoded before being handed back to callers.
+    /// Disabled by default; enable it for endpoints that return large JSON feeds.
+    pub fn with_compression(mut self, enabled: bool) -> Self {
+        self.accept_compression = enabled;
+        self
+    }
+
+    /// Whether compressed transfer negotiation is enabled
+    pub fn compression_enabled(&self) -> bool {
+        self.accept_compression
+    }
+
+    /// Restrict which codecs [`with_compression`](Self::with_compression) advertises and decodes
+    ///
+    /// Useful to drop Brotli's decode cost when it's never the smallest
+    /// response, or to match an intermediary that mishandles one of the codecs.
+    /// Has no effect unless compression negotiation is also enabled.
+    pub fn with_compression_encodings(mut self, encodings: CompressionEncodings) -> Self {
+        self.compression_encodings = encodings;
+        self
+    }
+
+    /// Which codecs compression negotiation advertises and decodes
+    pub fn compression_encodings(&self) -> CompressionEncodings {
+        self.compression_encodings
+    }
+
+    /// Opt into caching [`Self::get_cached`] responses for `ttl`
+    ///
+    /// Repeated lookups of the same URL within `ttl` are served from memory
+    /// instead of re-hitting Pixiv, which also helps stay under rate limits.
+    /// Only [`Self::get_cached`] consults the cache; [`Self::get`] always
+    /// hits the network.
+    pub fn with_cache(mut self, ttl: StdDuration) -> Self {
+        self.cache = Some(Arc::new(Mutex::new(ResponseCache::new(ttl))));
+        self
+    }
+
+    /// Override the cache TTL for URLs containing `path_prefix` (e.g. `/v1/user/detail`)
+    ///
+    /// No-op if [`with_cache`](Self::with_cache) hasn't been called yet.
+    pub fn with_endpoint_cache_ttl(self, path_prefix: impl Into<String>, ttl: StdDuration) -> Self {
+        if let Some(cache) = &self.cache {
+            cache.lock().unwrap().set_endpoint_ttl(path_prefix.into(), ttl);
+        }
+        self
+    }
+
+    /// Whether response caching is enabled
+    pub fn cache_enabled(&self) -> bool {
+        self.cache.is_some()
+    }
+
+    /// `GET` a URL, transparently serving a cached value when one is fresh
+    ///
+    /// Falls back to a normal [`Self::get`] (and decodes the body as JSON) on
+    /// a cache miss, or unconditionally when no cache has been configured via
+    /// [`with_cache`](Self::with_cache).
+    pub async fn get_cached(&self, url: &str) -> Result<serde_json::Value> {
+        if let Some(cache) = &self.cache {
+            if let Some(value) = cache.lock().unwrap().get(url) {
+                debug!(url = %url, "Cache hit");
+                return Ok(value);
+            }
+        }
+
+        let value: serde_json::Value = self.get(url).await?.json().await?;
+
+        if let Some(cache) = &self.cache {
+            cache.lock().unwrap().put(url.to_string(), value.clone());
+        }
+
+        Ok(value)
+    }
+
+    /// Opt into an `ETag`/`Last-Modified` revalidation cache, backed by the default
+    /// in-process [`InMemoryConditionalCache`]
+    ///
+    /// Unlike [`Self::with_cache`], every lookup still hits the network, but a
+    /// `304 Not Modified` response skips re-fetching and -deserializing the body.
+    /// Use [`Self::with_conditional_cache_store`] to plug in a different backend.
+    pub fn with_conditional_cache(mut self, config: CacheConfig) -> Self {
+        self.conditional_cache = Some(InMemoryConditionalCache::new(config));
+        self
+    }
+
+    /// Opt into an `ETag`/`Last-Modified` revalidation cache backed by a custom [`ConditionalCacheStore`]
+    pub fn with_conditional_cache_store(mut self, store: Arc<dyn ConditionalCacheStore>) -> Self {
+        self.conditional_cache = Some(store);
+        self
+    }
+
+    /// Whether conditional-request caching is enabled
+    pub fn conditional_cache_enabled(&self) -> bool {
+        self.conditional_cache.is_some()
+    }
+
+    /// Opt into recording one structured [`AccessLogEntry`] per [`Self::send_request`] call to `sink`
+    ///
+    /// [`FileAccessLogSink`] covers appending JSON lines to a file;
+    /// [`CallbackAccessLogSink`] forwards entries into an existing logging pipeline.
+    pub fn with_access_log_sink(mut self, sink: Arc<dyn AccessLogSink>) -> Self {
+        self.access_log = Some(sink);
+        self
+    }
+
+    /// Whether an access-log sink is configured
+    pub fn access_log_enabled(&self) -> bool {
+        self.access_log.is_some()
+    }
+
+    /// `GET` a URL, revalidating a previously cached value with `If-None-Match`/`If-Modified-Since`
+    ///
+    /// On a `304 Not Modified` the cached, already-deserialized value is returned
+    /// without paying for another JSON parse. Falls back to a plain [`Self::get`]
+    /// (and decodes the body as JSON) when no conditional cache has been
+    /// configured via [`Self::with_conditional_cache`]/[`Self::with_conditional_cache_store`].
+    pub async fn get_conditional(&self, url: &str) -> Result<serde_json::Value> {
+        let Some(cache) = &self.conditional_cache else {
+            return self.get(url).await?.json().await;
+        };
+
+        let cached = cache.get(url);
+
+        let mut request = self.client.get(url);
+        if let Some(token) = self.access_token() {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+        if let Some(entry) = &cached {
+            if let Some(etag) = &entry.etag {
+                request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = request.send().await.map_err(NetworkError::RequestError)?;
+
+        let status = response.status();
+
+        if status == reqwest::StatusCode::NOT_MODIFIED {
+            if let Some(entry) = cached {
+                debug!(url = %url, "Conditional cache hit (304 Not Modified)");
+                return Ok(entry.value);
+            }
+        }
+
+        if !status.is_success() {
+            return Err(PixivError::ApiError(format!(
+                "API request failed: {} - {}",
+                status,
+                response.text().await.unwrap_or_else(|_| "Failed to get error information".to_string())
+            )));
+        }
+
+        let etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+        let last_modified = response
+            .headers()
+            .get(reqwest::header::LAST_MODIFIED)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+
+        let value: serde_json::Value = response.json().await.map_err(NetworkError::RequestError)?;
+
+        debug!(url = %url, has_etag = etag.is_some(), "Conditional cache miss, storing fresh response");
+        cache.put(url.to_string(), CachedResponse::new(etag, last_modified, value.clone()));
+
+        Ok(value)
     }
 
     /// Set authentication token
-    pub fn set_access_token(&mut self, token: String) {
-        self.access_token = Some(token);
+    ///
+    /// Takes `&self` since the token is stored behind a shared lock: this lets
+    /// a [`ReauthHandler`] update the token from within `send_request` without
+    /// needing exclusive access to the client.
+    pub fn set_access_token(&self, token: String) {
+        *self.access_token.lock().unwrap() = Some(token);
     }
 
     /// Get current authentication token
-    pub fn access_token(&self) -> Option<&str> {
-        self.access_token.as_deref()
+    pub fn access_token(&self) -> Option<String> {
+        self.access_token.lock().unwrap().clone()
     }
 
     /// Set refresh token
-    pub fn set_refresh_token(&mut self, token: String) {
-        self.refresh_token = Some(token);
+    pub fn set_refresh_token(&self, token: String) {
+        *self.refresh_token.lock().unwrap() = Some(token);
     }
 
     /// Get current refresh token
-    pub fn refresh_token(&self) -> Option<&str> {
-        self.refresh_token.as_deref()
+    pub fn refresh_token(&self) -> Option<String> {
+        self.refresh_token.lock().unwrap().clone()
     }
 
     /// Send GET request
-    pub async fn get(&self, url: &str) -> Result<Response> {
+    pub async fn get(&self, url: &str) -> Result<HttpResponse> {
         self.send_request(reqwest::Method::GET, url, None::<&()>).await
     }
 
     /// Send POST request
-    pub async fn post<T: Serialize + ?Sized>(&self, url: &str, body: &T) -> Result<Response> {
+    pub async fn post<T: Serialize + ?Sized>(&self, url: &str, body: &T) -> Result<HttpResponse> {
         self.send_request(reqwest::Method::POST, url, Some(body)).await
     }
 
+    /// Send GET request without negotiating compression
+    ///
+    /// Intended for streaming binary downloads (e.g. images), where transparent
+    /// decompression would be wasted work and the raw bytes are what's needed.
+    pub async fn get_raw(&self, url: &str) -> Result<HttpResponse> {
+        self.send_request_impl(reqwest::Method::GET, url, None::<&()>, None).await
+    }
+
     /// Send authenticated API request
     pub async fn send_request<T: Serialize + ?Sized>(
         &self,
         method: reqwest::Method,
         url: &str,
         body: Option<&T>,
-    ) -> Result<Response> {
-        debug!(method = %method, url = %url, "Sending API request");
+    ) -> Result<HttpResponse> {
+        let encodings = self.accept_compression.then_some(self.compression_encodings);
+        let start = std::time::Instant::now();
+        let result = self.send_request_impl(method.clone(), url, body, encodings).await;
 
-        let mut request = self.client.request(method.clone(), url);
+        if let Some(sink) = &self.access_log {
+            let elapsed = start.elapsed();
+            let entry = match &result {
+                Ok(response) => access_log::build_entry(
+                    method.as_str(),
+                    url,
+                    Some(response.status().as_u16()),
+                    None,
+                    response.body_len(),
+                    elapsed,
+                ),
+                Err(PixivError::ApiErrorWithDetails { details }) => {
+                    access_log::build_entry(method.as_str(), url, None, Some(details.code.clone()), 0, elapsed)
+                }
+                Err(_) => access_log::build_entry(method.as_str(), url, None, None, 0, elapsed),
+            };
+            sink.record(&entry);
+        }
 
-        // Add authentication header
-        if let Some(token) = &self.access_token {
-            request = request.header("Authorization", format!("Bearer {}", token));
+        result
+    }
+
+    #[tracing::instrument(
+        name = "pixiv_http_request",
+        skip(self, body, encodings),
+        fields(
+            http.method = %method,
+            endpoint = %url,
+            http.status_code = tracing::field::Empty,
+            retry_count = tracing::field::Empty,
+        )
+    )]
+    async fn send_request_impl<T: Serialize + ?Sized>(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        body: Option<&T>,
+        encodings: Option<CompressionEncodings>,
+    ) -> Result<HttpResponse> {
+        let mut attempt = 0u32;
+        let mut reauthed = false;
+
+        if let (Some(handler), Some(skew)) = (&self.reauth, self.proactive_reauth_skew) {
+            if handler.should_refresh(skew) {
+                match handler.refresh().await {
+                    Ok(new_token) => {
+                        debug!("Proactively refreshed access token before expiry");
+                        self.set_access_token(new_token);
+                    }
+                    Err(e) => warn!(error = %e, "Proactive token refresh failed, continuing with existing token"),
+                }
+            }
         }
 
-        // Add request body
-        if let Some(body) = body {
-            request = request.json(body);
+        loop {
+            debug!(method = %method, url = %url, attempt = attempt, "Sending API request");
+
+            let mut request = self.client.request(method.clone(), url);
+
+            // Add authentication header
+            let token = if let Some(backend) = &self.auth_backend {
+                match backend.token().await {
+                    Ok(token) => Some(token),
+                    Err(e) => return Err(PixivError::AuthError(format!("Auth backend failed to supply a token: {}", e))),
+                }
+            } else {
+                self.access_token()
+            };
+            if let Some(token) = token {
+                request = request.header("Authorization", format!("Bearer {}", token));
+            }
+
+            // Negotiate compressed transfer
+            if let Some(header) = encodings.and_then(|e| e.accept_encoding_header()) {
+                request = request.header(reqwest::header::ACCEPT_ENCODING, header);
+            }
+
+            // Add request body
+            if let Some(body) = body {
+                request = request.json(body);
+            }
+
+            // Send request
+            let response = match request.send().await {
+                Ok(response) => response,
+                Err(err) if self.retry_policy.enabled
+                    && is_transient_transport_error(&err)
+                    && attempt < self.retry_policy.max_retries =>
+                {
+                    let wait = self.computed_backoff(attempt);
+                    warn!(attempt = attempt, wait_secs = wait.as_secs_f64(), error = %err, "Network request failed, backing off before retry");
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                    continue;
+                }
+                Err(err) => {
+                    tracing::Span::current().record("retry_count", attempt);
+                    return Err(err.into());
+                }
+            };
+            let status = response.status();
+            tracing::Span::current().record("http.status_code", status.as_u16()).record("retry_count", attempt);
+
+            if let Some(info) = RateLimitInfo::from_headers(response.headers()) {
+                *self.rate_limit.lock().unwrap() = Some(info);
+            }
+
+            let is_throttled = status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                || status == reqwest::StatusCode::SERVICE_UNAVAILABLE;
+
+            if is_throttled {
+                let wait = self.rate_limit_backoff(response.headers(), attempt);
+
+                if self.retry_policy.enabled && attempt < self.retry_policy.max_retries {
+                    warn!(attempt = attempt, wait_secs = wait.as_secs_f64(), "Rate limited, backing off before retry");
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                    continue;
+                }
+
+                if !self.retry_policy.enabled {
+                    return Err(PixivError::RateLimited { retry_after: wait });
+                }
+
+                let code = ApiErrorCode::from(status.as_str());
+                let headers = headers_to_map(response.headers());
+                let body = response.text().await.ok();
+                return Err(PixivError::ApiErrorWithDetails {
+                    details: ApiErrorDetails { code, message: format!("Rate limited after {} attempts", attempt + 1), headers: Some(headers), body },
+                });
+            }
+
+            if status == reqwest::StatusCode::UNAUTHORIZED && !reauthed {
+                if let Some(backend) = &self.auth_backend {
+                    reauthed = true;
+                    match backend.refresh().await {
+                        Ok(_) => {
+                            warn!("Access token expired, refreshed via auth backend and retrying request once");
+                            continue;
+                        }
+                        Err(e) => {
+                            return Err(PixivError::AuthError(format!(
+                                "Access token expired and auth backend refresh failed: {}",
+                                e
+                            )));
+                        }
+                    }
+                } else if let Some(handler) = &self.reauth {
+                    reauthed = true;
+                    match handler.refresh().await {
+                        Ok(new_token) => {
+                            warn!("Access token expired, refreshed and retrying request once");
+                            self.set_access_token(new_token);
+                            continue;
+                        }
+                        Err(e) => {
+                            return Err(PixivError::AuthError(format!(
+                                "Access token expired and refresh failed: {}",
+                                e
+                            )));
+                        }
+                    }
+                }
+            }
+
+            // Check response status
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_else(|_| "Failed to get error information".to_string());
+
+                // Pixiv sometimes reports an expired/invalid token as a
+                // body-level `103` error code instead of (or alongside) a
+                // bare 401, so the same transparent re-auth-and-retry
+                // applies here, not just to the 401 branch above.
+                if !reauthed && matches!(extract_api_error_code(&body), Some(ApiErrorCode::AuthError103)) {
+                    if let Some(backend) = &self.auth_backend {
+                        reauthed = true;
+                        match backend.refresh().await {
+                            Ok(_) => {
+                                warn!("Received AuthError103, refreshed via auth backend and retrying request once");
+                                continue;
+                            }
+                            Err(e) => {
+                                return Err(PixivError::AuthError(format!(
+                                    "Received AuthError103 and auth backend refresh failed: {}",
+                                    e
+                                )));
+                            }
+                        }
+                    } else if let Some(handler) = &self.reauth {
+                        reauthed = true;
+                        match handler.refresh().await {
+                            Ok(new_token) => {
+                                warn!("Received AuthError103, refreshed and retrying request once");
+                                self.set_access_token(new_token);
+                                continue;
+                            }
+                            Err(e) => {
+                                return Err(PixivError::AuthError(format!(
+                                    "Received AuthError103 and refresh failed: {}",
+                                    e
+                                )));
+                            }
+                        }
+                    }
+                }
+
+                return Err(PixivError::ApiError(format!("API request failed: {} - {}", status, body)));
+            }
+
+            let decoded = HttpResponse::from_reqwest(response, encodings).await?;
+
+            debug!(status = %status, content_encoding = ?decoded.content_encoding(), "API request completed successfully");
+            return Ok(decoded);
         }
+    }
 
-        // Send request
-        let response = request.send().await?;
+    /// Compute how long to sleep before retrying a rate-limited request
+    ///
+    /// Prefers the server-provided `Retry-After` header, falls back to the
+    /// parsed reset timestamp, and finally to exponential backoff, always
+    /// adding a small jitter to avoid a thundering herd of retries.
+    fn rate_limit_backoff(&self, headers: &reqwest::header::HeaderMap, attempt: u32) -> StdDuration {
+        let jitter = StdDuration::from_millis((rand::random::<f64>() * 250.0) as u64);
 
-        // Check response status
-        if !response.status().is_success() {
-            return Err(PixivError::ApiError(format!(
-                "API request failed: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_else(|_| "Failed to get error information".to_string())
-            )));
+        if let Some(retry_after) = headers.get(reqwest::header::RETRY_AFTER).and_then(|value| value.to_str().ok()) {
+            if let Ok(secs) = retry_after.parse::<u64>() {
+                return StdDuration::from_secs(secs) + jitter;
+            }
+            if let Ok(at) = chrono::DateTime::parse_from_rfc2822(retry_after) {
+                let secs = (at.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_seconds().max(0) as u64;
+                return StdDuration::from_secs(secs) + jitter;
+            }
         }
 
-        debug!(status = %response.status(), "API request completed successfully");
-        Ok(response)
+        if let Some(reset_at) = RateLimitInfo::from_headers(headers).and_then(|info| info.reset_at) {
+            let secs = (reset_at - chrono::Utc::now()).num_seconds().max(0) as u64;
+            return StdDuration::from_secs(secs) + jitter;
+        }
+
+        self.computed_backoff(attempt)
+    }
+
+    /// Exponential backoff for `attempt` (0-based), capped at
+    /// [`RetryPolicy::max_delay`], plus jitter in `[0, delay/2]`
+    ///
+    /// Used both as the fallback for rate-limited responses lacking a
+    /// `Retry-After`/reset header, and for retrying raw transport errors.
+    fn computed_backoff(&self, attempt: u32) -> StdDuration {
+        let exp = self.retry_policy.base_delay.saturating_mul(2u32.saturating_pow(attempt));
+        let delay = exp.min(self.retry_policy.max_delay);
+        let jitter = delay.mul_f64(rand::random::<f64>() * 0.5);
+        delay + jitter
     }
 
     /// Get API base URL
@@ -131,6 +857,29 @@ impl HttpClient {
     }
 }
 
+/// Whether `err` is a timeout or connection failure worth retrying, as
+/// opposed to e.g. a URL-building or body-encoding error
+fn is_transient_transport_error(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_connect()
+}
+
+/// Flatten a header map into a plain `String -> String` map for [`ApiErrorDetails::headers`]
+fn headers_to_map(headers: &reqwest::header::HeaderMap) -> HashMap<String, String> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
+
+/// Pull the Pixiv-reported error code out of `{"error": {"code": "..."}}`-shaped
+/// response bodies, for callers (e.g. the `103`-triggered re-auth above) that
+/// need to react to a body-level code rather than just the HTTP status
+fn extract_api_error_code(body: &str) -> Option<ApiErrorCode> {
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    let code = value.get("error")?.get("code")?.as_str()?;
+    Some(ApiErrorCode::from(code))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,10 +888,93 @@ mod tests {
     fn test_generate_security_headers() {
         let client = HttpClient::new().unwrap();
         let headers = client.generate_security_headers();
-        
+
         assert!(headers.contains_key("x-client-time"));
         assert!(headers.contains_key("x-client-hash"));
         assert!(!headers.get("x-client-time").unwrap().is_empty());
         assert!(!headers.get("x-client-hash").unwrap().is_empty());
     }
+
+    #[test]
+    fn test_retry_policy_defaults() {
+        let policy = RetryPolicy::default();
+        assert!(policy.enabled);
+        assert_eq!(policy.base_delay, DEFAULT_RETRY_BASE_DELAY);
+        assert_eq!(policy.max_delay, DEFAULT_RETRY_MAX_DELAY);
+        assert_eq!(policy.max_retries, DEFAULT_MAX_RATE_LIMIT_RETRIES);
+    }
+
+    #[test]
+    fn test_with_retry_policy_overrides_defaults() {
+        let client = HttpClient::new().unwrap().with_retry_policy(RetryPolicy {
+            enabled: false,
+            base_delay: StdDuration::from_millis(10),
+            max_delay: StdDuration::from_millis(100),
+            max_retries: 1,
+        });
+
+        let policy = client.retry_policy();
+        assert!(!policy.enabled);
+        assert_eq!(policy.base_delay, StdDuration::from_millis(10));
+        assert_eq!(policy.max_delay, StdDuration::from_millis(100));
+        assert_eq!(policy.max_retries, 1);
+    }
+
+    #[test]
+    fn test_compression_encodings_accept_header() {
+        assert_eq!(CompressionEncodings::ALL.accept_encoding_header().as_deref(), Some("gzip, deflate, br"));
+        assert_eq!(CompressionEncodings::NONE.accept_encoding_header(), None);
+
+        let gzip_only = CompressionEncodings { gzip: true, deflate: false, br: false };
+        assert_eq!(gzip_only.accept_encoding_header().as_deref(), Some("gzip"));
+    }
+
+    #[test]
+    fn test_with_compression_encodings_overrides_default() {
+        let client = HttpClient::new().unwrap().with_compression_encodings(CompressionEncodings::NONE);
+        assert_eq!(client.compression_encodings(), CompressionEncodings::NONE);
+    }
+
+    #[test]
+    fn test_computed_backoff_is_capped_at_max_delay() {
+        let client = HttpClient::new().unwrap().with_retry_policy(RetryPolicy {
+            enabled: true,
+            base_delay: StdDuration::from_millis(500),
+            max_delay: StdDuration::from_secs(1),
+            max_retries: 10,
+        });
+
+        // Attempt 10 would be 500ms * 2^10 without a cap, vastly exceeding max_delay
+        let wait = client.computed_backoff(10);
+        assert!(wait <= StdDuration::from_secs(1) + StdDuration::from_millis(500));
+    }
+
+    #[test]
+    fn test_computed_backoff_grows_exponentially_before_the_cap() {
+        let client = HttpClient::new().unwrap().with_retry_policy(RetryPolicy {
+            enabled: true,
+            base_delay: StdDuration::from_millis(100),
+            max_delay: StdDuration::from_secs(60),
+            max_retries: 10,
+        });
+
+        let first = client.computed_backoff(0);
+        let second = client.computed_backoff(1);
+        // Jitter is at most delay/2, so attempt 1's floor (200ms) still exceeds
+        // attempt 0's ceiling (100ms + 50ms jitter)
+        assert!(second >= StdDuration::from_millis(200));
+        assert!(first <= StdDuration::from_millis(150));
+    }
+
+    #[test]
+    fn test_extract_api_error_code_reads_body_level_103() {
+        let body = r#"{"error": {"code": "103", "message": "invalid token"}}"#;
+        assert!(matches!(extract_api_error_code(body), Some(ApiErrorCode::AuthError103)));
+    }
+
+    #[test]
+    fn test_extract_api_error_code_missing_or_malformed_is_none() {
+        assert!(extract_api_error_code("not json").is_none());
+        assert!(extract_api_error_code(r#"{"message": "no error field"}"#).is_none());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,69 @@
+use chrono::{DateTime, TimeZone, Utc};
+use reqwest::header::HeaderMap;
+
+/// Rate-limit state parsed from the most recent response
+///
+/// Populated from `X-RateLimit-*` style headers, modeled on the rate-limit
+/// surface APIs such as imgur expose on every call.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimitInfo {
+    /// Requests remaining in the current window
+    pub remaining: Option<u32>,
+    /// Total requests allowed per window
+    pub limit: Option<u32>,
+    /// When the current window resets
+    pub reset_at: Option<DateTime<Utc>>,
+}
+
+impl RateLimitInfo {
+    /// Parse rate-limit headers from a response, returning `None` if none were present
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let remaining = header_u32(headers, "x-ratelimit-remaining");
+        let limit = header_u32(headers, "x-ratelimit-limit");
+        let reset_at = header_str(headers, "x-ratelimit-reset")
+            .and_then(|value| value.parse::<i64>().ok())
+            .and_then(|ts| Utc.timestamp_opt(ts, 0).single());
+
+        if remaining.is_none() && limit.is_none() && reset_at.is_none() {
+            return None;
+        }
+
+        Some(Self {
+            remaining,
+            limit,
+            reset_at,
+        })
+    }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+fn header_u32(headers: &HeaderMap, name: &str) -> Option<u32> {
+    header_str(headers, name).and_then(|value| value.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_from_headers_parses_known_fields() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("10"));
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("100"));
+
+        let info = RateLimitInfo::from_headers(&headers).unwrap();
+        assert_eq!(info.remaining, Some(10));
+        assert_eq!(info.limit, Some(100));
+        assert_eq!(info.reset_at, None);
+    }
+
+    #[test]
+    fn test_from_headers_absent() {
+        let headers = HeaderMap::new();
+        assert!(RateLimitInfo::from_headers(&headers).is_none());
+    }
+}
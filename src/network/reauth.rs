@@ -0,0 +1,18 @@
+use crate::error::Result;
+use std::time::Duration;
+
+/// Pluggable hook that lets [`HttpClient`](crate::network::HttpClient) recover
+/// from an expired access token without the network layer depending on
+/// `AuthClient` directly.
+///
+/// `AuthClient` implements this trait; attach it via
+/// [`HttpClient::with_reauth_handler`](crate::network::HttpClient::with_reauth_handler).
+#[async_trait::async_trait]
+pub trait ReauthHandler: std::fmt::Debug + Send + Sync {
+    /// Refresh the access token, returning the new one on success
+    async fn refresh(&self) -> Result<String>;
+
+    /// Whether the current token is within `skew` of expiring and should be
+    /// refreshed proactively, before the next request is sent
+    fn should_refresh(&self, skew: Duration) -> bool;
+}
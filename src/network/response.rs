@@ -0,0 +1,166 @@
+use super::CompressionEncodings;
+use crate::error::{NetworkError, Result};
+use reqwest::header::HeaderMap;
+use reqwest::{Response, StatusCode};
+use serde::de::DeserializeOwned;
+
+/// HTTP response wrapper that transparently decodes compressed bodies
+///
+/// Mirrors the subset of `reqwest::Response` the rest of the crate relies on
+/// (`status`, `headers`, `text`, `bytes`, `json`), but eagerly decodes the body
+/// when a negotiated `Content-Encoding` is present so callers never see raw
+/// compressed bytes.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    content_encoding: Option<String>,
+    body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Build a response, decoding the body if `encodings` negotiated (and the
+    /// server honored) a `Content-Encoding` it allows
+    ///
+    /// `encodings` is `None` when compression wasn't negotiated for this
+    /// request (e.g. [`HttpClient::get_raw`](super::HttpClient::get_raw)), in
+    /// which case the raw bytes are always passed through unchanged.
+    pub(crate) async fn from_reqwest(
+        response: Response,
+        encodings: Option<CompressionEncodings>,
+    ) -> Result<Self> {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let content_encoding = headers
+            .get(reqwest::header::CONTENT_ENCODING)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+
+        let raw = response.bytes().await?.to_vec();
+
+        let body = match (encodings, content_encoding.as_deref()) {
+            (Some(enc), Some("gzip")) if enc.gzip => decode_gzip(&raw)?,
+            (Some(enc), Some("deflate")) if enc.deflate => decode_deflate(&raw)?,
+            (Some(enc), Some("br")) if enc.br => decode_brotli(&raw)?,
+            // No negotiation, an encoding we didn't enable, or `identity`:
+            // pass the bytes through unchanged.
+            _ => raw,
+        };
+
+        Ok(Self {
+            status,
+            headers,
+            content_encoding,
+            body,
+        })
+    }
+
+    /// Response status code
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Response headers
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// The `Content-Encoding` that was negotiated for this response, if any
+    ///
+    /// Set even when the body has already been transparently decoded, so
+    /// callers can log or verify which encoding the server chose.
+    pub fn content_encoding(&self) -> Option<&str> {
+        self.content_encoding.as_deref()
+    }
+
+    /// Size of the (decoded) body in bytes, without consuming the response
+    pub fn body_len(&self) -> usize {
+        self.body.len()
+    }
+
+    /// Consume the response, returning the (decoded) body as bytes
+    pub async fn bytes(self) -> Result<Vec<u8>> {
+        Ok(self.body)
+    }
+
+    /// Consume the response, returning the (decoded) body as text
+    pub async fn text(self) -> Result<String> {
+        Ok(String::from_utf8_lossy(&self.body).into_owned())
+    }
+
+    /// Consume the response, deserializing the (decoded) body as JSON
+    pub async fn json<T: DeserializeOwned>(self) -> Result<T> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+}
+
+fn decode_gzip(data: &[u8]) -> Result<Vec<u8>> {
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    let mut decoder = GzDecoder::new(data);
+    let mut out = Vec::new();
+    decoder.read_to_end(&mut out).map_err(|source| NetworkError::DecompressionError {
+        encoding: "gzip".to_string(),
+        source,
+    })?;
+    Ok(out)
+}
+
+fn decode_deflate(data: &[u8]) -> Result<Vec<u8>> {
+    use flate2::read::DeflateDecoder;
+    use std::io::Read;
+
+    let mut decoder = DeflateDecoder::new(data);
+    let mut out = Vec::new();
+    decoder.read_to_end(&mut out).map_err(|source| NetworkError::DecompressionError {
+        encoding: "deflate".to_string(),
+        source,
+    })?;
+    Ok(out)
+}
+
+fn decode_brotli(data: &[u8]) -> Result<Vec<u8>> {
+    use std::io::Read;
+
+    let mut decoder = brotli::Decompressor::new(data, 4096);
+    let mut out = Vec::new();
+    decoder.read_to_end(&mut out).map_err(|source| NetworkError::DecompressionError {
+        encoding: "br".to_string(),
+        source,
+    })?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_gzip_roundtrip() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"hello pixiv").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let decoded = decode_gzip(&compressed).unwrap();
+        assert_eq!(decoded, b"hello pixiv");
+    }
+
+    #[test]
+    fn test_decode_deflate_roundtrip() {
+        use flate2::write::DeflateEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"hello pixiv").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let decoded = decode_deflate(&compressed).unwrap();
+        assert_eq!(decoded, b"hello pixiv");
+    }
+}
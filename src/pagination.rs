@@ -0,0 +1,300 @@
+//! Async pagination over Pixiv's `next_url` cursor convention
+//!
+//! Most list-shaped responses (`SearchIllustResponse`, `UserFollowingResponse`,
+//! `RankingResponse`, ...) carry a homogeneous `Vec<T>` plus an `Option<String>
+//! next_url` pointing at the following page. [`Paginated`] exposes that shape
+//! uniformly, and [`Pager`] turns it into a [`futures::Stream`] that walks
+//! every page transparently.
+
+use crate::error::Result;
+use crate::network::bypass_sni::BypassSniClient;
+use crate::network::HttpClient;
+use futures::stream::Stream;
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A response page carrying a homogeneous item list and a `next_url` cursor
+pub trait Paginated<T> {
+    /// The items on this page
+    fn items(&self) -> &[T];
+    /// The URL of the next page, or `None` if this is the last page
+    fn next_url(&self) -> Option<&str>;
+}
+
+type PageFuture<R> = Pin<Box<dyn Future<Output = Result<R>> + Send>>;
+
+/// Async stream that walks every page of a [`Paginated`] response type `R`,
+/// yielding one item `T` at a time.
+///
+/// The first page is fetched lazily, on the first call to `poll_next`. Once
+/// the buffered page is drained, a GET is issued against the previous page's
+/// `next_url` (through [`HttpClient`], so auth headers are re-attached the
+/// same way as any other request) to refill the buffer. The stream ends
+/// cleanly once a page's `next_url` is `None`.
+pub struct Pager<R, T> {
+    client: HttpClient,
+    next_url: Option<String>,
+    buffer: VecDeque<T>,
+    in_flight: Option<PageFuture<R>>,
+}
+
+impl<R, T> Pager<R, T> {
+    /// Create a pager that starts by fetching `first_url`
+    pub fn new(client: HttpClient, first_url: impl Into<String>) -> Self {
+        Self {
+            client,
+            next_url: Some(first_url.into()),
+            buffer: VecDeque::new(),
+            in_flight: None,
+        }
+    }
+}
+
+impl<R, T> Pager<R, T>
+where
+    R: Paginated<T> + DeserializeOwned + Send + 'static,
+    T: Clone,
+{
+    /// Collect up to `limit` items, stopping early once that many have been
+    /// yielded rather than draining the whole stream
+    pub async fn collect_n(self, limit: usize) -> Result<Vec<T>> {
+        use futures::stream::StreamExt;
+
+        let mut items = Vec::with_capacity(limit);
+        let mut stream = Box::pin(self);
+        while items.len() < limit {
+            match stream.next().await {
+                Some(Ok(item)) => items.push(item),
+                Some(Err(e)) => return Err(e),
+                None => break,
+            }
+        }
+        Ok(items)
+    }
+}
+
+impl<R, T> Stream for Pager<R, T>
+where
+    R: Paginated<T> + DeserializeOwned + Send + 'static,
+    T: Clone,
+{
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(item) = this.buffer.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+
+            if this.in_flight.is_none() {
+                let Some(url) = this.next_url.take() else {
+                    return Poll::Ready(None);
+                };
+                let client = this.client.clone();
+                this.in_flight = Some(Box::pin(async move { client.get(&url).await?.json::<R>().await }));
+            }
+
+            match this.in_flight.as_mut().unwrap().as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) => {
+                    this.in_flight = None;
+                    return Poll::Ready(Some(Err(e)));
+                }
+                Poll::Ready(Ok(page)) => {
+                    this.in_flight = None;
+                    this.next_url = page.next_url().map(|s| s.to_string());
+                    this.buffer.extend(page.items().iter().cloned());
+                }
+            }
+        }
+    }
+}
+
+type BypassSniPageFuture<R> = Pin<Box<dyn Future<Output = Result<R>> + Send>>;
+
+/// [`Pager`] twin for [`BypassSniAppClient`](crate::client::bypass_sni::BypassSniAppClient)
+///
+/// Identical paging logic, but requests go through [`BypassSniClient`] so
+/// each page refetch gets the same proactive re-signing, token renewal and
+/// rate-limit backoff as any other SNI bypass request.
+pub struct BypassSniPager<R, T> {
+    client: BypassSniClient,
+    next_url: Option<String>,
+    buffer: VecDeque<T>,
+    in_flight: Option<BypassSniPageFuture<R>>,
+}
+
+impl<R, T> BypassSniPager<R, T> {
+    /// Create a pager that starts by fetching `first_url`
+    pub fn new(client: BypassSniClient, first_url: impl Into<String>) -> Self {
+        Self {
+            client,
+            next_url: Some(first_url.into()),
+            buffer: VecDeque::new(),
+            in_flight: None,
+        }
+    }
+
+    /// The URL the first page was (or will be) fetched from
+    #[cfg(test)]
+    pub(crate) fn first_url(&self) -> Option<&str> {
+        self.next_url.as_deref()
+    }
+}
+
+impl<R, T> BypassSniPager<R, T>
+where
+    R: Paginated<T> + DeserializeOwned + Send + 'static,
+    T: Clone,
+{
+    /// Collect up to `limit` items, stopping early once that many have been
+    /// yielded rather than draining the whole stream
+    pub async fn collect_n(self, limit: usize) -> Result<Vec<T>> {
+        use futures::stream::StreamExt;
+
+        let mut items = Vec::with_capacity(limit);
+        let mut stream = Box::pin(self);
+        while items.len() < limit {
+            match stream.next().await {
+                Some(Ok(item)) => items.push(item),
+                Some(Err(e)) => return Err(e),
+                None => break,
+            }
+        }
+        Ok(items)
+    }
+}
+
+impl<R, T> Stream for BypassSniPager<R, T>
+where
+    R: Paginated<T> + DeserializeOwned + Send + 'static,
+    T: Clone,
+{
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(item) = this.buffer.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+
+            if this.in_flight.is_none() {
+                let Some(url) = this.next_url.take() else {
+                    return Poll::Ready(None);
+                };
+                let client = this.client.clone();
+                this.in_flight = Some(Box::pin(async move { Ok(client.get(&url).await?.json::<R>().await?) }));
+            }
+
+            match this.in_flight.as_mut().unwrap().as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) => {
+                    this.in_flight = None;
+                    return Poll::Ready(Some(Err(e)));
+                }
+                Poll::Ready(Ok(page)) => {
+                    this.in_flight = None;
+                    this.next_url = page.next_url().map(|s| s.to_string());
+                    this.buffer.extend(page.items().iter().cloned());
+                }
+            }
+        }
+    }
+}
+
+macro_rules! impl_paginated {
+    ($ty:ty, $field:ident, $item:ty) => {
+        impl Paginated<$item> for $ty {
+            fn items(&self) -> &[$item] {
+                &self.$field
+            }
+
+            fn next_url(&self) -> Option<&str> {
+                self.next_url.as_deref()
+            }
+        }
+    };
+}
+
+mod app_impls {
+    use super::Paginated;
+    use crate::models::app::{
+        BookmarkTag, Comment, CommentsResponse, Illust, IllustFollowResponse, IllustNewResponse,
+        IllustRelatedResponse, Novel, NovelFollowResponse, NovelNewResponse, NovelRecommendedResponse,
+        NovelSeriesResponse, RankingResponse, RecommendedResponse, SearchIllustResponse, SearchNovelResponse,
+        SearchUserResponse, TrendingTag, TrendingTagsResponse, UserBookmarkTagsIllustResponse,
+        UserBookmarksNovelResponse, UserFollowerResponse, UserFollowingResponse, UserIllustrationsResponse,
+        UserListResponse, UserMypixivResponse, UserNovelsResponse, UserPreview, UserRecommendedResponse,
+        UserRelatedResponse,
+    };
+
+    impl_paginated!(CommentsResponse, comments, Comment);
+    impl_paginated!(IllustFollowResponse, illusts, Illust);
+    impl_paginated!(TrendingTagsResponse, trend_tags, TrendingTag);
+    impl_paginated!(UserFollowingResponse, user_previews, UserPreview);
+    impl_paginated!(UserFollowerResponse, user_previews, UserPreview);
+    impl_paginated!(IllustRelatedResponse, illusts, Illust);
+    impl_paginated!(IllustNewResponse, illusts, Illust);
+    impl_paginated!(UserRelatedResponse, user_previews, UserPreview);
+    impl_paginated!(UserRecommendedResponse, user_previews, UserPreview);
+    impl_paginated!(UserListResponse, user_previews, UserPreview);
+    impl_paginated!(UserBookmarkTagsIllustResponse, bookmark_tags, BookmarkTag);
+    impl_paginated!(SearchUserResponse, user_previews, UserPreview);
+    impl_paginated!(UserIllustrationsResponse, illusts, Illust);
+    impl_paginated!(UserMypixivResponse, user_previews, UserPreview);
+    impl_paginated!(RankingResponse, illusts, Illust);
+    impl_paginated!(RecommendedResponse, illusts, Illust);
+    impl_paginated!(SearchIllustResponse, illusts, Illust);
+    impl_paginated!(UserNovelsResponse, novels, Novel);
+    impl_paginated!(NovelSeriesResponse, novels, Novel);
+    impl_paginated!(NovelNewResponse, novels, Novel);
+    impl_paginated!(NovelFollowResponse, novels, Novel);
+    impl_paginated!(NovelRecommendedResponse, novels, Novel);
+    impl_paginated!(SearchNovelResponse, novels, Novel);
+    impl_paginated!(UserBookmarksNovelResponse, novels, Novel);
+}
+
+mod public_impls {
+    use super::Paginated;
+    use crate::models::public::{PublicIllust, PublicSearchResponse, PublicUserBookmarks, PublicUserIllusts};
+
+    impl_paginated!(PublicSearchResponse, illusts, PublicIllust);
+    impl_paginated!(PublicUserIllusts, illusts, PublicIllust);
+    impl_paginated!(PublicUserBookmarks, illusts, PublicIllust);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::app::IllustFollowResponse;
+
+    #[test]
+    fn test_paginated_exposes_items_and_next_url() {
+        let page: IllustFollowResponse = serde_json::from_value(serde_json::json!({
+            "illusts": [],
+            "next_url": "https://app-api.pixiv.net/v2/illust/follow?offset=30",
+        }))
+        .unwrap();
+
+        assert_eq!(page.items().len(), 0);
+        assert_eq!(page.next_url(), Some("https://app-api.pixiv.net/v2/illust/follow?offset=30"));
+    }
+
+    #[test]
+    fn test_paginated_last_page_has_no_next_url() {
+        let page: IllustFollowResponse = serde_json::from_value(serde_json::json!({
+            "illusts": [],
+            "next_url": null,
+        }))
+        .unwrap();
+
+        assert!(page.next_url().is_none());
+    }
+}
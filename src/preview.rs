@@ -0,0 +1,60 @@
+//! Terminal image preview for illustration thumbnails
+//!
+//! Renders an illustration's thumbnail inline in a terminal, using whatever
+//! graphics protocol the terminal supports (Kitty, iTerm2, sixel) and
+//! falling back to half-block characters otherwise. Entirely optional:
+//! gated behind the `preview` feature so non-CLI consumers don't pull in a
+//! terminal-graphics dependency.
+
+use crate::error::{PixivError, Result};
+use crate::models::public::PublicIllust;
+use crate::network::HttpClient;
+
+/// Options controlling how [`preview_illust`] renders a thumbnail
+#[derive(Debug, Clone)]
+pub struct PreviewOptions {
+    /// Maximum rendered width, in terminal columns
+    pub max_width: u32,
+    /// Maximum rendered height, in terminal rows
+    pub max_height: u32,
+    /// Print the illustration title and author above the image
+    pub show_caption: bool,
+}
+
+impl Default for PreviewOptions {
+    fn default() -> Self {
+        Self { max_width: 40, max_height: 20, show_caption: true }
+    }
+}
+
+/// Download, decode, and render an illustration's thumbnail in the terminal
+///
+/// Picks the smallest available preview URL from `illust.image_urls`
+/// (`square_medium`, then `medium`) since a thumbnail is all a terminal
+/// rendering needs. Returns [`PixivError::PreviewError`] if no preview URL
+/// is present on the illustration.
+pub async fn preview_illust(client: &HttpClient, illust: &PublicIllust, options: PreviewOptions) -> Result<()> {
+    let url = illust
+        .image_urls
+        .square_medium
+        .as_deref()
+        .or(illust.image_urls.medium.as_deref())
+        .ok_or_else(|| PixivError::PreviewError("Illustration has no preview image URL".to_string()))?;
+
+    let bytes = client.get_raw(url).await?.bytes().await?;
+    let image = image::load_from_memory(&bytes)
+        .map_err(|e| PixivError::PreviewError(format!("Failed to decode preview image: {}", e)))?;
+
+    if options.show_caption {
+        println!("{} — {}", illust.title, illust.user.name);
+    }
+
+    let config = viuer::Config {
+        width: Some(options.max_width),
+        height: Some(options.max_height),
+        ..Default::default()
+    };
+    viuer::print(&image, &config).map_err(|e| PixivError::PreviewError(format!("Failed to render preview: {}", e)))?;
+
+    Ok(())
+}
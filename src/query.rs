@@ -0,0 +1,437 @@
+//! Advanced search query DSL
+//!
+//! Pixiv's `word` search parameter only supports crude substring/tag
+//! matching, so this module lets callers express richer boolean queries —
+//! `tag:landscape -tag:ai AND bookmarks>1000` — that get split into (a) the
+//! broadest positive terms, pushed into Pixiv's native `word` param to
+//! narrow what gets fetched, and (b) the full expression, re-evaluated
+//! client-side against each returned [`Illust`] as the authoritative
+//! filter (Pixiv's own matching is fuzzy, so the native `word` param is
+//! only ever a prefilter, never trusted for correctness).
+//!
+//! Grammar, loosest to tightest binding:
+//! ```text
+//! expr    := and_expr (OR and_expr)*
+//! and_expr := unary (AND? unary)*        // AND may be implicit between adjacent terms
+//! unary   := NOT unary | '-' primary | primary
+//! primary := '(' expr ')' | numeric_cmp | tag_match | word
+//! numeric_cmp := field ('>' | '>=' | '<' | '<=' | '=') number
+//! tag_match := "tag:" bare_word
+//! ```
+
+use crate::models::app::Illust;
+use std::fmt;
+
+/// A parsed query expression, ready to be matched against an [`Illust`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryExpr {
+    And(Box<QueryExpr>, Box<QueryExpr>),
+    Or(Box<QueryExpr>, Box<QueryExpr>),
+    Not(Box<QueryExpr>),
+    /// `tag:foo` — matches if any of the illust's tags (or translations) equal `foo`
+    TagMatch(String),
+    /// A bare word — matches if it appears in the title or any tag
+    Word(String),
+    /// `field {op} value`, e.g. `bookmarks>1000`
+    NumericCmp(NumericField, CmpOp, f64),
+}
+
+/// Fields a [`QueryExpr::NumericCmp`] can compare against
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumericField {
+    Bookmarks,
+    Views,
+    Width,
+    Height,
+    UserId,
+}
+
+/// Comparison operators supported by [`QueryExpr::NumericCmp`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CmpOp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+}
+
+impl QueryExpr {
+    /// Evaluate this expression against `illust`
+    pub fn matches(&self, illust: &Illust) -> bool {
+        match self {
+            QueryExpr::And(lhs, rhs) => lhs.matches(illust) && rhs.matches(illust),
+            QueryExpr::Or(lhs, rhs) => lhs.matches(illust) || rhs.matches(illust),
+            QueryExpr::Not(inner) => !inner.matches(illust),
+            QueryExpr::TagMatch(tag) => illust.tags.iter().any(|t| {
+                t.name.eq_ignore_ascii_case(tag) || t.translated_name.as_deref().is_some_and(|tn| tn.eq_ignore_ascii_case(tag))
+            }),
+            QueryExpr::Word(word) => {
+                let word = word.to_lowercase();
+                illust.title.to_lowercase().contains(word.as_str())
+                    || illust.tags.iter().any(|t| t.name.to_lowercase().contains(word.as_str()))
+            }
+            QueryExpr::NumericCmp(field, op, value) => {
+                let actual = match field {
+                    NumericField::Bookmarks => illust.total_bookmarks as f64,
+                    NumericField::Views => illust.total_view as f64,
+                    NumericField::Width => illust.width as f64,
+                    NumericField::Height => illust.height as f64,
+                    NumericField::UserId => illust.user.id as f64,
+                };
+                match op {
+                    CmpOp::Gt => actual > *value,
+                    CmpOp::Ge => actual >= *value,
+                    CmpOp::Lt => actual < *value,
+                    CmpOp::Le => actual <= *value,
+                    CmpOp::Eq => (actual - *value).abs() < f64::EPSILON,
+                }
+            }
+        }
+    }
+
+    /// Collect every positive (non-negated) `Word`/`TagMatch` leaf into a
+    /// single space-joined string suitable for Pixiv's native `word` param
+    ///
+    /// This is a best-effort prefilter, not a faithful translation of the
+    /// expression (`OR`-ed and negated terms can't be pushed down), so
+    /// [`Self::matches`] always re-checks the full expression client-side.
+    pub fn word_hint(&self) -> String {
+        let mut terms = Vec::new();
+        self.collect_positive_terms(&mut terms, false);
+        terms.join(" ")
+    }
+
+    fn collect_positive_terms(&self, terms: &mut Vec<String>, negated: bool) {
+        match self {
+            QueryExpr::And(lhs, rhs) | QueryExpr::Or(lhs, rhs) => {
+                lhs.collect_positive_terms(terms, negated);
+                rhs.collect_positive_terms(terms, negated);
+            }
+            QueryExpr::Not(inner) => inner.collect_positive_terms(terms, !negated),
+            QueryExpr::TagMatch(tag) if !negated => terms.push(tag.clone()),
+            QueryExpr::Word(word) if !negated => terms.push(word.clone()),
+            _ => {}
+        }
+    }
+}
+
+/// A structured parse error, with the byte offset of the offending token
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryParseError {
+    pub message: String,
+    pub position: usize,
+}
+
+impl fmt::Display for QueryParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at position {})", self.message, self.position)
+    }
+}
+
+impl std::error::Error for QueryParseError {}
+
+/// Parse a query expression string into a [`QueryExpr`] AST
+pub fn parse(input: &str) -> Result<QueryExpr, QueryParseError> {
+    let tokens = tokenize(input);
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_or()?;
+    if let Some(tok) = parser.peek() {
+        return Err(QueryParseError { message: format!("Unexpected token '{}'", tok.text), position: tok.position });
+    }
+    Ok(expr)
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    text: String,
+    position: usize,
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(pos, ch)) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if ch == '(' || ch == ')' {
+            tokens.push(Token { text: ch.to_string(), position: pos });
+            chars.next();
+            continue;
+        }
+
+        let start = pos;
+        let mut atom = String::new();
+        while let Some(&(_, c)) = chars.peek() {
+            if c.is_whitespace() || c == '(' || c == ')' {
+                break;
+            }
+            atom.push(c);
+            chars.next();
+        }
+        tokens.push(Token { text: atom, position: start });
+    }
+
+    tokens
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        self.peek().is_some_and(|tok| tok.text.eq_ignore_ascii_case(keyword))
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    /// `expr := and_expr (OR and_expr)*`
+    fn parse_or(&mut self) -> Result<QueryExpr, QueryParseError> {
+        let mut lhs = self.parse_and()?;
+        while self.peek_keyword("OR") {
+            self.advance();
+            let rhs = self.parse_and()?;
+            lhs = QueryExpr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    /// `and_expr := unary ((AND)? unary)*`
+    fn parse_and(&mut self) -> Result<QueryExpr, QueryParseError> {
+        let mut lhs = self.parse_unary()?;
+        loop {
+            if self.peek_keyword("AND") {
+                self.advance();
+                let rhs = self.parse_unary()?;
+                lhs = QueryExpr::And(Box::new(lhs), Box::new(rhs));
+                continue;
+            }
+            // Implicit AND: another term follows with no operator or closing paren
+            if self.can_start_unary() {
+                let rhs = self.parse_unary()?;
+                lhs = QueryExpr::And(Box::new(lhs), Box::new(rhs));
+                continue;
+            }
+            break;
+        }
+        Ok(lhs)
+    }
+
+    fn can_start_unary(&self) -> bool {
+        match self.peek() {
+            None => false,
+            Some(tok) => !tok.text.eq_ignore_ascii_case("OR") && !tok.text.eq_ignore_ascii_case("AND") && tok.text != ")",
+        }
+    }
+
+    /// `unary := NOT unary | '-' primary | primary`
+    fn parse_unary(&mut self) -> Result<QueryExpr, QueryParseError> {
+        if self.peek_keyword("NOT") {
+            self.advance();
+            return Ok(QueryExpr::Not(Box::new(self.parse_unary()?)));
+        }
+        if let Some(tok) = self.peek() {
+            if let Some(rest) = tok.text.strip_prefix('-') {
+                if !rest.is_empty() {
+                    let position = tok.position;
+                    let inner_text = rest.to_string();
+                    self.advance();
+                    let inner = parse_primary_atom(&inner_text, position)?;
+                    return Ok(QueryExpr::Not(Box::new(inner)));
+                }
+            }
+        }
+        self.parse_primary()
+    }
+
+    /// `primary := '(' expr ')' | atom`
+    fn parse_primary(&mut self) -> Result<QueryExpr, QueryParseError> {
+        match self.advance() {
+            Some(tok) if tok.text == "(" => {
+                let inner = self.parse_or()?;
+                match self.advance() {
+                    Some(close) if close.text == ")" => Ok(inner),
+                    Some(other) => Err(QueryParseError { message: "Expected ')'".to_string(), position: other.position }),
+                    None => Err(QueryParseError { message: "Expected ')'".to_string(), position: tok.position }),
+                }
+            }
+            Some(tok) => parse_primary_atom(&tok.text, tok.position),
+            None => Err(QueryParseError { message: "Unexpected end of query".to_string(), position: usize::MAX }),
+        }
+    }
+}
+
+/// Parse one non-paren atom (already split off by the tokenizer) into a leaf [`QueryExpr`]
+fn parse_primary_atom(atom: &str, position: usize) -> Result<QueryExpr, QueryParseError> {
+    if let Some(tag) = atom.strip_prefix("tag:") {
+        if tag.is_empty() {
+            return Err(QueryParseError { message: "Empty tag after 'tag:'".to_string(), position });
+        }
+        return Ok(QueryExpr::TagMatch(tag.to_string()));
+    }
+
+    if let Some((field, op, value)) = split_numeric_cmp(atom) {
+        let field = parse_field(field).ok_or_else(|| QueryParseError { message: format!("Unknown field '{}'", field), position })?;
+        let op = parse_op(op).ok_or_else(|| QueryParseError { message: format!("Unknown operator '{}'", op), position })?;
+        let value: f64 = value
+            .parse()
+            .map_err(|_| QueryParseError { message: format!("Invalid numeric value '{}'", value), position })?;
+        return Ok(QueryExpr::NumericCmp(field, op, value));
+    }
+
+    if atom.is_empty() {
+        return Err(QueryParseError { message: "Empty term".to_string(), position });
+    }
+
+    Ok(QueryExpr::Word(atom.to_string()))
+}
+
+/// Split an atom like `bookmarks>=1000` into `("bookmarks", ">=", "1000")`
+fn split_numeric_cmp(atom: &str) -> Option<(&str, &str, &str)> {
+    let ops = [">=", "<=", ">", "<", "="];
+    for op in ops {
+        if let Some(idx) = atom.find(op) {
+            if idx == 0 {
+                continue;
+            }
+            return Some((&atom[..idx], op, &atom[idx + op.len()..]));
+        }
+    }
+    None
+}
+
+fn parse_field(field: &str) -> Option<NumericField> {
+    match field.to_lowercase().as_str() {
+        "bookmarks" => Some(NumericField::Bookmarks),
+        "views" => Some(NumericField::Views),
+        "width" => Some(NumericField::Width),
+        "height" => Some(NumericField::Height),
+        "user_id" | "userid" => Some(NumericField::UserId),
+        _ => None,
+    }
+}
+
+fn parse_op(op: &str) -> Option<CmpOp> {
+    match op {
+        ">" => Some(CmpOp::Gt),
+        ">=" => Some(CmpOp::Ge),
+        "<" => Some(CmpOp::Lt),
+        "<=" => Some(CmpOp::Le),
+        "=" => Some(CmpOp::Eq),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::app::{ContentType, ImageUrls, MetaSinglePage, ProfileImageUrls, Tag, User};
+
+    fn sample_illust(tags: &[&str], total_bookmarks: u64, width: u32, user_id: u64) -> Illust {
+        Illust {
+            id: 1,
+            title: "A mountain landscape".to_string(),
+            illust_type: ContentType::Illust,
+            image_urls: ImageUrls { square_medium: String::new(), medium: String::new(), large: String::new() },
+            caption: String::new(),
+            restrict: 0,
+            user: User {
+                id: user_id,
+                name: "artist".to_string(),
+                account: "artist".to_string(),
+                profile_image_urls: ProfileImageUrls { medium: String::new() },
+                comment: None,
+                is_followed: None,
+            },
+            tags: tags.iter().map(|t| Tag { name: t.to_string(), translated_name: None }).collect(),
+            tools: Vec::new(),
+            create_date: String::new(),
+            page_count: 1,
+            width,
+            height: 1080,
+            sanity_level: 0,
+            x_restrict: 0,
+            series: None,
+            meta_single_page: MetaSinglePage { original_image_url: None },
+            meta_pages: Vec::new(),
+            total_view: 0,
+            total_bookmarks,
+            is_bookmarked: false,
+            visible: true,
+            is_muted: false,
+            illust_ai_type: 0,
+            illust_book_style: 0,
+            total_comments: None,
+            comment_access_control: None,
+            restriction_attributes: None,
+        }
+    }
+
+    #[test]
+    fn test_tag_and_numeric_cmp() {
+        let ast = parse("tag:landscape AND bookmarks>1000").unwrap();
+        assert!(ast.matches(&sample_illust(&["landscape"], 1500, 1920, 1)));
+        assert!(!ast.matches(&sample_illust(&["landscape"], 500, 1920, 1)));
+    }
+
+    #[test]
+    fn test_implicit_and_between_adjacent_terms() {
+        let ast = parse("tag:landscape bookmarks>1000").unwrap();
+        assert!(ast.matches(&sample_illust(&["landscape"], 1500, 1920, 1)));
+        assert!(!ast.matches(&sample_illust(&["portrait"], 1500, 1920, 1)));
+    }
+
+    #[test]
+    fn test_negation_excludes_tag() {
+        let ast = parse("tag:landscape -tag:ai").unwrap();
+        assert!(ast.matches(&sample_illust(&["landscape"], 0, 1920, 1)));
+        assert!(!ast.matches(&sample_illust(&["landscape", "ai"], 0, 1920, 1)));
+    }
+
+    #[test]
+    fn test_or_has_lower_precedence_than_and() {
+        // "a AND b OR c" == "(a AND b) OR c"
+        let ast = parse("tag:a tag:b OR tag:c").unwrap();
+        assert!(ast.matches(&sample_illust(&["c"], 0, 1920, 1)));
+        assert!(!ast.matches(&sample_illust(&["a"], 0, 1920, 1)));
+        assert!(ast.matches(&sample_illust(&["a", "b"], 0, 1920, 1)));
+    }
+
+    #[test]
+    fn test_parentheses_override_precedence() {
+        let ast = parse("tag:a AND (tag:b OR tag:c)").unwrap();
+        assert!(ast.matches(&sample_illust(&["a", "c"], 0, 1920, 1)));
+        assert!(!ast.matches(&sample_illust(&["a"], 0, 1920, 1)));
+    }
+
+    #[test]
+    fn test_word_hint_collects_positive_terms_only() {
+        let ast = parse("tag:landscape -tag:ai bookmarks>1000").unwrap();
+        assert_eq!(ast.word_hint(), "landscape");
+    }
+
+    #[test]
+    fn test_parse_error_reports_position() {
+        let err = parse("tag:a AND (tag:b").unwrap_err();
+        assert_eq!(err.message, "Expected ')'");
+    }
+
+    #[test]
+    fn test_parse_error_on_unknown_field() {
+        let err = parse("nonsense>5").unwrap_err();
+        assert!(err.message.contains("Unknown field"));
+    }
+}
@@ -0,0 +1,231 @@
+//! Persistent SQLite-backed store for [`crate::client::cached::CachedAppClient`]
+//!
+//! Responses are kept in a single `requests` table keyed by a canonical
+//! request signature (method + endpoint + sorted params, hashed), alongside
+//! the raw JSON body and an `inserted_at` timestamp. This lets the cache
+//! survive process restarts, unlike [`crate::network`]'s in-memory TTL cache.
+
+use crate::error::{PixivError, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A canonical key identifying one request, independent of param ordering
+///
+/// Built from the HTTP method, endpoint path, and sorted `(name, value)`
+/// params, hashed with the same `md5` crate [`crate::network::HttpClient`]
+/// already depends on for its security headers.
+pub fn request_key(method: &str, endpoint: &str, params: &[(&str, String)]) -> String {
+    let mut sorted = params.to_vec();
+    sorted.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut canonical = format!("{} {}", method, endpoint);
+    for (name, value) in &sorted {
+        canonical.push('&');
+        canonical.push_str(name);
+        canonical.push('=');
+        canonical.push_str(value);
+    }
+
+    format!("{:x}", md5::compute(canonical))
+}
+
+/// SQLite-backed persistent cache for raw JSON responses
+pub struct RequestStore {
+    conn: Mutex<Connection>,
+}
+
+impl RequestStore {
+    /// Open (creating if necessary) a store backed by the SQLite database at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let conn = Connection::open(path).map_err(|e| PixivError::StoreError(format!("Failed to open store: {}", e)))?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS requests (
+                key TEXT PRIMARY KEY,
+                endpoint TEXT NOT NULL,
+                illust_id INTEGER,
+                body TEXT NOT NULL,
+                inserted_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| PixivError::StoreError(format!("Failed to create requests table: {}", e)))?;
+
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// The cached value for `key`, if present and younger than `ttl`
+    pub fn get(&self, key: &str, ttl: Duration) -> Result<Option<serde_json::Value>> {
+        let conn = self.conn.lock().unwrap();
+        let row: Option<(String, i64)> = conn
+            .query_row("SELECT body, inserted_at FROM requests WHERE key = ?1", params![key], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .optional()
+            .map_err(|e| PixivError::StoreError(format!("Failed to read cached response: {}", e)))?;
+
+        let Some((body, inserted_at)) = row else {
+            return Ok(None);
+        };
+
+        let age = now_unix().saturating_sub(inserted_at.max(0) as u64);
+        if age >= ttl.as_secs() {
+            return Ok(None);
+        }
+
+        let value = serde_json::from_str(&body)?;
+        Ok(Some(value))
+    }
+
+    /// Store `value` under `key`, optionally tagging it with the illust it
+    /// belongs to so [`Self::invalidate`] can find it later
+    pub fn put(&self, key: &str, endpoint: &str, illust_id: Option<u64>, value: &serde_json::Value) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO requests (key, endpoint, illust_id, body, inserted_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![key, endpoint, illust_id, value.to_string(), now_unix() as i64],
+        )
+        .map_err(|e| PixivError::StoreError(format!("Failed to write cached response: {}", e)))?;
+        Ok(())
+    }
+
+    /// The cached value for `key` regardless of age, for stale-if-error fallback
+    pub fn get_stale(&self, key: &str) -> Result<Option<serde_json::Value>> {
+        let conn = self.conn.lock().unwrap();
+        let body: Option<String> = conn
+            .query_row("SELECT body FROM requests WHERE key = ?1", params![key], |row| row.get(0))
+            .optional()
+            .map_err(|e| PixivError::StoreError(format!("Failed to read cached response: {}", e)))?;
+
+        Ok(match body {
+            Some(body) => Some(serde_json::from_str(&body)?),
+            None => None,
+        })
+    }
+
+    /// Every cached response body stored under `endpoint`, regardless of TTL
+    ///
+    /// Unlike [`Self::get`], this doesn't key on a single request signature,
+    /// so it's useful for bulk-reconstructing derived state (e.g. rebuilding
+    /// a [`crate::index::SearchIndex`]) from everything already cached.
+    pub fn list_by_endpoint(&self, endpoint: &str) -> Result<Vec<serde_json::Value>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare("SELECT body FROM requests WHERE endpoint = ?1")
+            .map_err(|e| PixivError::StoreError(format!("Failed to prepare endpoint scan: {}", e)))?;
+        let rows = stmt
+            .query_map(params![endpoint], |row| row.get::<_, String>(0))
+            .map_err(|e| PixivError::StoreError(format!("Failed to scan cached rows: {}", e)))?;
+
+        let mut values = Vec::new();
+        for row in rows {
+            let body = row.map_err(|e| PixivError::StoreError(format!("Failed to read cached row: {}", e)))?;
+            values.push(serde_json::from_str(&body)?);
+        }
+        Ok(values)
+    }
+
+    /// Remove every cached row tagged with `illust_id`
+    pub fn invalidate(&self, illust_id: u64) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM requests WHERE illust_id = ?1", params![illust_id])
+            .map_err(|e| PixivError::StoreError(format!("Failed to invalidate cached rows: {}", e)))?;
+        Ok(())
+    }
+
+    /// If the table holds more than `max_rows` rows, delete the oldest ones
+    /// (by `inserted_at`) until it doesn't
+    pub fn evict_oldest(&self, max_rows: usize) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM requests", [], |row| row.get(0))
+            .map_err(|e| PixivError::StoreError(format!("Failed to count cached rows: {}", e)))?;
+
+        let excess = count - max_rows as i64;
+        if excess <= 0 {
+            return Ok(());
+        }
+
+        conn.execute(
+            "DELETE FROM requests WHERE key IN (
+                SELECT key FROM requests ORDER BY inserted_at ASC LIMIT ?1
+            )",
+            params![excess],
+        )
+        .map_err(|e| PixivError::StoreError(format!("Failed to evict oldest cached rows: {}", e)))?;
+        Ok(())
+    }
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_key_ignores_param_order() {
+        let a = request_key("GET", "/v1/illust/detail", &[("illust_id", "1".to_string()), ("filter", "for_ios".to_string())]);
+        let b = request_key("GET", "/v1/illust/detail", &[("filter", "for_ios".to_string()), ("illust_id", "1".to_string())]);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_put_then_get_round_trips_within_ttl() {
+        let store = RequestStore::open(":memory:").unwrap();
+        let key = request_key("GET", "/v1/illust/detail", &[("illust_id", "1".to_string())]);
+        store.put(&key, "/v1/illust/detail", Some(1), &serde_json::json!({"id": 1})).unwrap();
+
+        let cached = store.get(&key, Duration::from_secs(60)).unwrap();
+        assert_eq!(cached, Some(serde_json::json!({"id": 1})));
+    }
+
+    #[test]
+    fn test_invalidate_removes_tagged_rows() {
+        let store = RequestStore::open(":memory:").unwrap();
+        let key = request_key("GET", "/v1/illust/detail", &[("illust_id", "1".to_string())]);
+        store.put(&key, "/v1/illust/detail", Some(1), &serde_json::json!({"id": 1})).unwrap();
+
+        store.invalidate(1).unwrap();
+        assert_eq!(store.get(&key, Duration::from_secs(60)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_get_stale_returns_value_past_ttl() {
+        let store = RequestStore::open(":memory:").unwrap();
+        let key = request_key("GET", "/v1/illust/detail", &[("illust_id", "1".to_string())]);
+        store.put(&key, "/v1/illust/detail", Some(1), &serde_json::json!({"id": 1})).unwrap();
+
+        assert_eq!(store.get(&key, Duration::from_secs(0)).unwrap(), None, "fresh lookup should respect a zero TTL");
+        assert_eq!(store.get_stale(&key).unwrap(), Some(serde_json::json!({"id": 1})));
+    }
+
+    #[test]
+    fn test_list_by_endpoint_returns_every_matching_body() {
+        let store = RequestStore::open(":memory:").unwrap();
+        let key_a = request_key("GET", "/v1/illust/detail", &[("illust_id", "1".to_string())]);
+        let key_b = request_key("GET", "/v1/illust/detail", &[("illust_id", "2".to_string())]);
+        store.put(&key_a, "/v1/illust/detail", Some(1), &serde_json::json!({"id": 1})).unwrap();
+        store.put(&key_b, "/v1/illust/detail", Some(2), &serde_json::json!({"id": 2})).unwrap();
+
+        let mut bodies = store.list_by_endpoint("/v1/illust/detail").unwrap();
+        bodies.sort_by_key(|v| v["id"].as_i64());
+        assert_eq!(bodies, vec![serde_json::json!({"id": 1}), serde_json::json!({"id": 2})]);
+    }
+
+    #[test]
+    fn test_evict_oldest_caps_row_count() {
+        let store = RequestStore::open(":memory:").unwrap();
+        for i in 0..5 {
+            let key = request_key("GET", "/v1/illust/detail", &[("illust_id", i.to_string())]);
+            store.put(&key, "/v1/illust/detail", Some(i), &serde_json::json!({"id": i})).unwrap();
+        }
+
+        store.evict_oldest(2).unwrap();
+        let count: i64 = store.conn.lock().unwrap().query_row("SELECT COUNT(*) FROM requests", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 2);
+    }
+}
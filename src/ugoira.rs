@@ -0,0 +1,398 @@
+//! Ugoira (Pixiv's animated illustration format) reconstruction
+//!
+//! `UgoiraMetadata` only describes the animation shape (frame order plus
+//! per-frame delay); the pixel data itself lives in a ZIP archive pointed to
+//! by `ZipUrls`. This module downloads that archive, unpacks it in memory,
+//! and re-encodes the frames into a standard animated format.
+
+use crate::client::app::AppClient;
+use crate::error::{PixivError, Result};
+use crate::models::app::{UgoiraMetadata, ZipUrls};
+use crate::network::HttpClient;
+use crate::utils::safe_filename;
+use image::RgbaImage;
+use std::io::{Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Which [`ZipUrls`] variant to download
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZipVariant {
+    /// Medium-size preview ZIP
+    Medium,
+    /// Large-size preview ZIP
+    Large,
+    /// Original, full-resolution ZIP
+    Original,
+}
+
+impl ZipVariant {
+    fn url(self, urls: &ZipUrls) -> &str {
+        match self {
+            ZipVariant::Medium => &urls.medium,
+            ZipVariant::Large => &urls.large,
+            ZipVariant::Original => &urls.original,
+        }
+    }
+}
+
+/// Download the chosen ZIP variant and assemble it into an animated GIF in one call
+pub async fn fetch_and_encode_gif(
+    client: &HttpClient,
+    metadata: &UgoiraMetadata,
+    variant: ZipVariant,
+) -> Result<Vec<u8>> {
+    let zip_bytes = client.get_raw(variant.url(&metadata.zip_urls)).await?.bytes().await?;
+    encode_gif(metadata, &zip_bytes)
+}
+
+/// Unpack `zip_bytes` and decode each frame named in `metadata.frames`,
+/// in listed order, pairing each with its delay in milliseconds
+fn decode_frames(metadata: &UgoiraMetadata, zip_bytes: &[u8]) -> Result<Vec<(RgbaImage, u32)>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(zip_bytes))
+        .map_err(|e| PixivError::UgoiraError(format!("Failed to open ugoira archive: {}", e)))?;
+
+    metadata
+        .frames
+        .iter()
+        .map(|frame| {
+            let mut entry = archive.by_name(&frame.file).map_err(|e| {
+                PixivError::UgoiraError(format!("Missing frame {} in archive: {}", frame.file, e))
+            })?;
+
+            let mut bytes = Vec::new();
+            entry
+                .read_to_end(&mut bytes)
+                .map_err(|e| PixivError::UgoiraError(format!("Failed to read frame {}: {}", frame.file, e)))?;
+
+            let image = image::load_from_memory(&bytes)
+                .map_err(|e| PixivError::UgoiraError(format!("Failed to decode frame {}: {}", frame.file, e)))?
+                .to_rgba8();
+
+            Ok((image, frame.delay))
+        })
+        .collect()
+}
+
+/// Assemble a Pixiv ugoira ZIP into an animated GIF
+///
+/// Frames are emitted in the order listed in `metadata.frames`, each held for
+/// its own `delay` (converted from milliseconds to the GIF spec's
+/// hundredths-of-a-second unit), and the result loops infinitely.
+pub fn encode_gif(metadata: &UgoiraMetadata, zip_bytes: &[u8]) -> Result<Vec<u8>> {
+    let frames = decode_frames(metadata, zip_bytes)?;
+    let mut output = Vec::new();
+    write_gif(&frames, &mut output)?;
+    Ok(output)
+}
+
+fn write_gif(frames: &[(RgbaImage, u32)], writer: impl Write) -> Result<()> {
+    let (width, height) = frames
+        .first()
+        .map(|(image, _)| (image.width() as u16, image.height() as u16))
+        .ok_or_else(|| PixivError::UgoiraError("Ugoira metadata has no frames".to_string()))?;
+
+    let mut encoder = gif::Encoder::new(writer, width, height, &[])
+        .map_err(|e| PixivError::UgoiraError(format!("Failed to start GIF encoder: {}", e)))?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(|e| PixivError::UgoiraError(format!("Failed to set GIF loop mode: {}", e)))?;
+
+    for (image, delay_ms) in frames {
+        let mut gif_frame = gif::Frame::from_rgba_speed(width, height, &mut image.clone().into_raw(), 10);
+        gif_frame.delay = gif_delay_centiseconds(*delay_ms);
+        encoder
+            .write_frame(&gif_frame)
+            .map_err(|e| PixivError::UgoiraError(format!("Failed to write GIF frame: {}", e)))?;
+    }
+
+    Ok(())
+}
+
+/// Convert a frame's millisecond delay to the GIF spec's hundredths-of-a-second
+/// unit, rounding to the nearest centisecond (instead of truncating) and
+/// clamping to a `u16`, with a floor of `1` since GIF viewers treat a `0`
+/// delay as "as fast as possible" rather than "instant"
+fn gif_delay_centiseconds(delay_ms: u32) -> u16 {
+    ((delay_ms + 5) / 10).clamp(1, u16::MAX as u32) as u16
+}
+
+/// Output format for [`ugoira_to_animation`]
+///
+/// Limited to the formats this crate already has an encoder for
+/// ([`UgoiraFrames::encode_gif`]/`encode_apng`/`encode_webp`); muxed video
+/// containers (mp4/webm) would need a video-muxing dependency this crate
+/// doesn't otherwise pull in, so they're out of scope here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UgoiraFormat {
+    /// Animated GIF
+    Gif,
+    /// Animated PNG
+    #[cfg(feature = "apng")]
+    Apng,
+    /// Animated WebP
+    #[cfg(feature = "webp")]
+    Webp,
+}
+
+/// Fetch ugoira metadata and frames for `id`, then encode as `format`,
+/// returning the encoded bytes alongside the animation's total duration
+/// (the sum of every frame's delay)
+pub async fn ugoira_to_animation(
+    app_client: &AppClient,
+    id: u64,
+    variant: ZipVariant,
+    format: UgoiraFormat,
+) -> Result<(Vec<u8>, Duration)> {
+    let frames = download_ugoira(app_client, id, variant).await?;
+    let duration = frames.total_duration();
+
+    let mut output = Vec::new();
+    match format {
+        UgoiraFormat::Gif => frames.encode_gif(&mut output)?,
+        #[cfg(feature = "apng")]
+        UgoiraFormat::Apng => frames.encode_apng(&mut output)?,
+        #[cfg(feature = "webp")]
+        UgoiraFormat::Webp => output = frames.encode_webp()?,
+    }
+
+    Ok((output, duration))
+}
+
+/// Decoded ugoira frames, ready to be encoded into an animated format
+///
+/// Frames are stored in the order `metadata.frames` declared (not ZIP entry
+/// order), each paired with its own millisecond delay, so callers can
+/// encode into whichever animated format they need without re-downloading.
+pub struct UgoiraFrames {
+    frames: Vec<(RgbaImage, u32)>,
+}
+
+impl UgoiraFrames {
+    /// Download the chosen ZIP variant and decode every frame, in declared order
+    pub async fn download(client: &HttpClient, metadata: &UgoiraMetadata, variant: ZipVariant) -> Result<Self> {
+        let zip_bytes = client.get_raw(variant.url(&metadata.zip_urls)).await?.bytes().await?;
+        Ok(Self { frames: decode_frames(metadata, &zip_bytes)? })
+    }
+
+    /// Number of decoded frames
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether there are no decoded frames
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Total animation duration: the sum of every frame's delay
+    pub fn total_duration(&self) -> Duration {
+        Duration::from_millis(self.frames.iter().map(|(_, delay_ms)| *delay_ms as u64).sum())
+    }
+
+    /// Encode as an animated GIF, writing the result to `writer`
+    pub fn encode_gif(&self, writer: impl Write) -> Result<()> {
+        write_gif(&self.frames, writer)
+    }
+
+    /// Encode as an animated PNG (APNG), writing the result to `writer`
+    #[cfg(feature = "apng")]
+    pub fn encode_apng(&self, writer: impl Write) -> Result<()> {
+        write_apng(&self.frames, writer)
+    }
+
+    /// Encode as an animated WebP
+    #[cfg(feature = "webp")]
+    pub fn encode_webp(&self) -> Result<Vec<u8>> {
+        encode_webp_frames(&self.frames)
+    }
+}
+
+/// Fetch ugoira metadata for `id` via `app_client`, then download and decode its frames
+pub async fn download_ugoira(app_client: &AppClient, id: u64, variant: ZipVariant) -> Result<UgoiraFrames> {
+    let metadata = app_client.ugoira_metadata(id).await?.ugoira_metadata;
+    UgoiraFrames::download(app_client.http_client(), &metadata, variant).await
+}
+
+/// File extension [`ugoira_to_animation`] writes for a given [`UgoiraFormat`]
+fn format_extension(format: UgoiraFormat) -> &'static str {
+    match format {
+        UgoiraFormat::Gif => "gif",
+        #[cfg(feature = "apng")]
+        UgoiraFormat::Apng => "png",
+        #[cfg(feature = "webp")]
+        UgoiraFormat::Webp => "webp",
+    }
+}
+
+/// Fetch, assemble, and write a ugoira animation into `dir`, returning the written path
+///
+/// Builds the filename from `id` and `format`'s extension via [`safe_filename`];
+/// the actual fetch/encode is delegated to [`ugoira_to_animation`].
+pub async fn download_ugoira_to_file(
+    app_client: &AppClient,
+    id: u64,
+    variant: ZipVariant,
+    format: UgoiraFormat,
+    dir: &Path,
+) -> Result<PathBuf> {
+    let (bytes, _duration) = ugoira_to_animation(app_client, id, variant, format).await?;
+
+    let filename = safe_filename(&format!("{}.{}", id, format_extension(format)));
+    let path = dir.join(filename);
+    tokio::fs::write(&path, &bytes)
+        .await
+        .map_err(|e| PixivError::UgoiraError(format!("Failed to write {}: {}", path.display(), e)))?;
+
+    Ok(path)
+}
+
+/// Assemble a Pixiv ugoira ZIP into an animated PNG (APNG)
+///
+/// Same frame order and timing semantics as [`encode_gif`], but without the
+/// 256-color palette limitation.
+#[cfg(feature = "apng")]
+pub fn encode_apng(metadata: &UgoiraMetadata, zip_bytes: &[u8]) -> Result<Vec<u8>> {
+    let frames = decode_frames(metadata, zip_bytes)?;
+    let mut output = Vec::new();
+    write_apng(&frames, &mut output)?;
+    Ok(output)
+}
+
+#[cfg(feature = "apng")]
+fn write_apng(frames: &[(RgbaImage, u32)], writer: impl Write) -> Result<()> {
+    let (width, height) = frames
+        .first()
+        .map(|(image, _)| (image.width(), image.height()))
+        .ok_or_else(|| PixivError::UgoiraError("Ugoira metadata has no frames".to_string()))?;
+
+    let encoder_config = apng::Config {
+        width,
+        height,
+        num_frames: frames.len() as u32,
+        num_plays: 0, // loop infinitely
+        color: png::ColorType::Rgba,
+        depth: png::BitDepth::Eight,
+        filter: png::FilterType::NoFilter,
+    };
+    let mut encoder = apng::Encoder::new(writer, encoder_config)
+        .map_err(|e| PixivError::UgoiraError(format!("Failed to start APNG encoder: {}", e)))?;
+
+    for (image, delay_ms) in frames {
+        let frame = apng::Frame {
+            delay_num: Some(apng_delay_num(*delay_ms)),
+            delay_den: Some(1000),
+            ..Default::default()
+        };
+        encoder
+            .write_frame(&image.clone().into_raw(), frame)
+            .map_err(|e| PixivError::UgoiraError(format!("Failed to write APNG frame: {}", e)))?;
+    }
+    encoder
+        .finish_encode()
+        .map_err(|e| PixivError::UgoiraError(format!("Failed to finalize APNG: {}", e)))?;
+
+    Ok(())
+}
+
+/// Clamp a frame's millisecond delay to a `u16` numerator, paired with the
+/// fixed `delay_den: 1000` denominator, so the delay is `delay_ms` milliseconds
+/// unchanged (no unit conversion needed, unlike the GIF centisecond format)
+#[cfg(feature = "apng")]
+fn apng_delay_num(delay_ms: u32) -> u16 {
+    delay_ms.min(u16::MAX as u32) as u16
+}
+
+/// Assemble a Pixiv ugoira ZIP into an animated WebP
+///
+/// Same frame order and timing semantics as [`encode_gif`].
+#[cfg(feature = "webp")]
+pub fn encode_webp(metadata: &UgoiraMetadata, zip_bytes: &[u8]) -> Result<Vec<u8>> {
+    let frames = decode_frames(metadata, zip_bytes)?;
+    encode_webp_frames(&frames)
+}
+
+/// Turn per-frame millisecond delays into the cumulative millisecond
+/// timestamp each frame starts at (`webp_animation::Encoder::add_frame`
+/// takes a start timestamp, not a duration), i.e. an exclusive prefix sum
+#[cfg(feature = "webp")]
+fn webp_frame_timestamps(delays_ms: &[u32]) -> Vec<i32> {
+    let mut timestamp_ms: i32 = 0;
+    let mut timestamps = Vec::with_capacity(delays_ms.len());
+    for delay_ms in delays_ms {
+        timestamps.push(timestamp_ms);
+        timestamp_ms += *delay_ms as i32;
+    }
+    timestamps
+}
+
+#[cfg(feature = "webp")]
+fn encode_webp_frames(frames: &[(RgbaImage, u32)]) -> Result<Vec<u8>> {
+    let (width, height) = frames
+        .first()
+        .map(|(image, _)| (image.width(), image.height()))
+        .ok_or_else(|| PixivError::UgoiraError("Ugoira metadata has no frames".to_string()))?;
+
+    let mut encoder = webp_animation::Encoder::new((width, height))
+        .map_err(|e| PixivError::UgoiraError(format!("Failed to start WebP encoder: {:?}", e)))?;
+
+    let delays_ms: Vec<u32> = frames.iter().map(|(_, delay_ms)| *delay_ms).collect();
+    let timestamps_ms = webp_frame_timestamps(&delays_ms);
+
+    for ((image, _), &timestamp_ms) in frames.iter().zip(&timestamps_ms) {
+        encoder
+            .add_frame(&image.clone().into_raw(), timestamp_ms)
+            .map_err(|e| PixivError::UgoiraError(format!("Failed to write WebP frame: {:?}", e)))?;
+    }
+
+    let total_ms = timestamps_ms.last().copied().unwrap_or(0) + delays_ms.last().copied().unwrap_or(0) as i32;
+    encoder
+        .finalize(total_ms)
+        .map(|data| data.to_vec())
+        .map_err(|e| PixivError::UgoiraError(format!("Failed to finalize WebP: {:?}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gif_delay_centiseconds_rounds_to_nearest() {
+        assert_eq!(gif_delay_centiseconds(16), 2);
+        assert_eq!(gif_delay_centiseconds(14), 1);
+        assert_eq!(gif_delay_centiseconds(25), 3);
+        assert_eq!(gif_delay_centiseconds(20), 2);
+    }
+
+    #[test]
+    fn test_gif_delay_centiseconds_floors_at_one() {
+        assert_eq!(gif_delay_centiseconds(0), 1);
+        assert_eq!(gif_delay_centiseconds(4), 1);
+    }
+
+    #[test]
+    fn test_gif_delay_centiseconds_clamps_to_u16_max() {
+        assert_eq!(gif_delay_centiseconds(u32::MAX), u16::MAX);
+    }
+
+    #[cfg(feature = "apng")]
+    #[test]
+    fn test_apng_delay_num_passes_milliseconds_through_unchanged() {
+        assert_eq!(apng_delay_num(16), 16);
+        assert_eq!(apng_delay_num(1000), 1000);
+    }
+
+    #[cfg(feature = "apng")]
+    #[test]
+    fn test_apng_delay_num_clamps_to_u16_max() {
+        assert_eq!(apng_delay_num(u32::MAX), u16::MAX);
+    }
+
+    #[cfg(feature = "webp")]
+    #[test]
+    fn test_webp_frame_timestamps_is_exclusive_prefix_sum() {
+        assert_eq!(webp_frame_timestamps(&[16, 16, 33]), vec![0, 16, 32]);
+        assert_eq!(webp_frame_timestamps(&[]), Vec::<i32>::new());
+        assert_eq!(webp_frame_timestamps(&[100]), vec![0]);
+    }
+}
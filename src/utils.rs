@@ -4,18 +4,28 @@
 
 use crate::error::{PixivError, Result};
 use crate::network::HttpClient;
+use futures::TryStreamExt;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
 use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
-/// Download image to specified path
+/// Download image to specified path, streaming the body directly to disk
+///
+/// The response is consumed chunk-by-chunk instead of buffering the whole
+/// image in memory, so large ugoira ZIPs and original illustrations don't
+/// blow up memory usage. An optional `on_progress` callback is invoked as
+/// bytes arrive with `(downloaded, total)`, where `total` comes from the
+/// `Content-Length` header and is `None` for chunked responses.
 ///
 /// # Arguments
 /// * `client` - HTTP client
 /// * `url` - Image URL
 /// * `path` - Save path
+/// * `on_progress` - Optional progress callback, invoked after each chunk is written
 ///
 /// # Returns
 /// Download result
@@ -24,24 +34,34 @@ use tracing::{debug, info};
 /// ```rust
 /// use pixiv_rs::utils::download;
 /// use pixiv_rs::network::HttpClient;
-/// 
+///
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
 ///     let client = HttpClient::new()?;
-///     download(&client, "https://example.com/image.jpg", "image.jpg").await?;
+///     download(&client, "https://example.com/image.jpg", "image.jpg".as_ref(), None::<fn(u64, Option<u64>)>).await?;
 ///     Ok(())
 /// }
 /// ```
-pub async fn download(
+pub async fn download<F>(
     client: &HttpClient,
     url: &str,
     path: &Path,
-) -> Result<()> {
+    mut on_progress: Option<F>,
+) -> Result<()>
+where
+    F: FnMut(u64, Option<u64>),
+{
     debug!(url = %url, path = ?path, "Starting download");
-    
-    // Send HTTP request to get image
-    let response = client.get(url).await?;
-    
+
+    // Build the request directly against the underlying reqwest client so we can
+    // stream the body instead of buffering it through `HttpClient::get`.
+    let mut request = client.client.get(url);
+    if let Some(token) = client.access_token() {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+
+    let response = request.send().await?;
+
     // Ensure request is successful
     if !response.status().is_success() {
         return Err(PixivError::ApiError(format!(
@@ -50,23 +70,142 @@ pub async fn download(
             response.status().canonical_reason().unwrap_or("Unknown error")
         )));
     }
-    
-    // Get image data
-    let bytes = response.bytes().await?;
-    
+
+    let total = response.content_length();
+
     // Create target file
     let mut file = File::create(path).await
         .map_err(|e| PixivError::Unknown(format!("Failed to create file {}: {}", path.display(), e)))?;
-    
-    // Write data
-    file.write_all(&bytes).await
-        .map_err(|e| PixivError::Unknown(format!("Failed to write file {}: {}", path.display(), e)))?;
-    
-    info!(url = %url, path = ?path, size = bytes.len(), "Download completed");
-    
+
+    let mut stream = response.bytes_stream();
+    let mut downloaded: u64 = 0;
+
+    while let Some(chunk) = stream.try_next().await? {
+        file.write_all(&chunk).await
+            .map_err(|e| PixivError::Unknown(format!("Failed to write file {}: {}", path.display(), e)))?;
+
+        downloaded += chunk.len() as u64;
+        if let Some(on_progress) = on_progress.as_mut() {
+            on_progress(downloaded, total);
+        }
+    }
+
+    info!(url = %url, path = ?path, size = downloaded, "Download completed");
+
     Ok(())
 }
 
+/// Like [`download`], but resumes a partially-downloaded file instead of
+/// overwriting it
+///
+/// If `path` already exists, the existing length is sent as a
+/// `Range: bytes=<len>-` header and new bytes are appended to the file. If
+/// the server doesn't support ranged requests (it replies `200 OK` instead
+/// of `206 Partial Content`), this falls back to a full download, truncating
+/// the partial file first so the two halves don't get concatenated.
+///
+/// # Example
+/// ```rust
+/// use pixiv_rs::utils::download_resumable;
+/// use pixiv_rs::network::HttpClient;
+///
+/// # async fn run() -> pixiv_rs::Result<()> {
+/// let client = HttpClient::new()?;
+/// download_resumable(&client, "https://example.com/image.jpg", "image.jpg".as_ref(), None::<fn(u64, Option<u64>)>).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn download_resumable<F>(
+    client: &HttpClient,
+    url: &str,
+    path: &Path,
+    mut on_progress: Option<F>,
+) -> Result<()>
+where
+    F: FnMut(u64, Option<u64>),
+{
+    let existing_len = tokio::fs::metadata(path).await.map(|m| m.len()).unwrap_or(0);
+
+    debug!(url = %url, path = ?path, existing_len = %existing_len, "Starting resumable download");
+
+    let mut request = client.client.get(url);
+    if let Some(token) = client.access_token() {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    if existing_len > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", existing_len));
+    }
+
+    let response = request.send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(PixivError::ApiError(format!(
+            "Download failed: {} - {}",
+            status,
+            status.canonical_reason().unwrap_or("Unknown error")
+        )));
+    }
+
+    let resuming = existing_len > 0 && status == reqwest::StatusCode::PARTIAL_CONTENT;
+    let mut downloaded = if resuming { existing_len } else { 0 };
+    let total = response.content_length().map(|len| downloaded + len);
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resuming)
+        .truncate(!resuming)
+        .open(path)
+        .await
+        .map_err(|e| PixivError::Unknown(format!("Failed to open file {}: {}", path.display(), e)))?;
+
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.try_next().await? {
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| PixivError::Unknown(format!("Failed to write file {}: {}", path.display(), e)))?;
+
+        downloaded += chunk.len() as u64;
+        if let Some(on_progress) = on_progress.as_mut() {
+            on_progress(downloaded, total);
+        }
+    }
+
+    info!(url = %url, path = ?path, size = downloaded, resumed = %resuming, "Resumable download completed");
+
+    Ok(())
+}
+
+/// Default progress reporter for [`download`], logging human-readable totals
+///
+/// # Example
+/// ```rust
+/// use pixiv_rs::utils::{download, log_progress};
+/// use pixiv_rs::network::HttpClient;
+///
+/// # async fn run() -> pixiv_rs::Result<()> {
+/// let client = HttpClient::new()?;
+/// download(&client, "https://example.com/image.jpg", "image.jpg".as_ref(), Some(log_progress)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn log_progress(downloaded: u64, total: Option<u64>) {
+    match total {
+        Some(total) if total > 0 => {
+            let percent = downloaded as f64 / total as f64 * 100.0;
+            info!(
+                downloaded = %format_file_size(downloaded),
+                total = %format_file_size(total),
+                percent = format!("{:.1}%", percent),
+                "Download progress"
+            );
+        }
+        _ => {
+            info!(downloaded = %format_file_size(downloaded), "Download progress");
+        }
+    }
+}
+
 /// Parse pagination parameters
 ///
 /// Parse query parameters from URL for pagination requests
@@ -243,6 +382,215 @@ pub fn extract_extension(url: &str) -> Option<String> {
     None
 }
 
+/// A single download job submitted to a [`BatchDownloader`]
+#[derive(Debug, Clone)]
+pub struct BatchJob {
+    /// Source URL
+    pub url: String,
+    /// Target path
+    pub path: PathBuf,
+    /// If the target already exists at this size, the job is skipped
+    /// instead of re-downloaded
+    pub expected_size: Option<u64>,
+}
+
+impl BatchJob {
+    /// Create a job that always downloads, even if `path` already exists
+    pub fn new(url: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self { url: url.into(), path: path.into(), expected_size: None }
+    }
+
+    /// Skip this job if `path` already exists and is `size` bytes long
+    pub fn with_expected_size(mut self, size: u64) -> Self {
+        self.expected_size = Some(size);
+        self
+    }
+}
+
+/// Outcome of a single [`BatchDownloader`] job
+#[derive(Debug, Clone)]
+pub struct BatchJobReport {
+    /// Source URL
+    pub url: String,
+    /// Path the file was written to (or already present at)
+    pub path: PathBuf,
+    /// Size of the file on disk after this job ran
+    pub bytes: u64,
+    /// Human-readable rendering of `bytes`, via [`format_file_size`]
+    pub size: String,
+    /// Whether the job was skipped because the target already existed at the expected size
+    pub skipped: bool,
+}
+
+/// Concurrency-bounded batch downloader for fetching many URLs at once
+/// without saturating Pixiv with unbounded parallel requests
+///
+/// Unlike [`crate::download`]'s worker-pool engine (built around a shared
+/// queue for illustration page sets), this drives an arbitrary iterator of
+/// `(url, path)` jobs through a [`tokio::sync::Semaphore`]-bounded fan-out,
+/// retrying each job independently with exponential backoff and optionally
+/// skipping files already downloaded at the expected size.
+pub struct BatchDownloader {
+    concurrency: usize,
+    max_attempts: u32,
+}
+
+impl BatchDownloader {
+    /// Create a downloader that runs at most `concurrency` jobs at once
+    pub fn new(concurrency: usize) -> Self {
+        Self { concurrency: concurrency.max(1), max_attempts: 3 }
+    }
+
+    /// Override the number of attempts per job before giving up on it
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Run every job, returning one result per job in submission order
+    pub async fn run(&self, client: &HttpClient, jobs: impl IntoIterator<Item = BatchJob>) -> Vec<Result<BatchJobReport>> {
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(self.concurrency));
+        let max_attempts = self.max_attempts;
+
+        let futures = jobs.into_iter().map(|job| {
+            let semaphore = semaphore.clone();
+            let client = client.clone();
+            async move {
+                let _permit = semaphore.acquire().await.expect("BatchDownloader semaphore closed");
+                run_batch_job(&client, job, max_attempts).await
+            }
+        });
+
+        futures::future::join_all(futures).await
+    }
+}
+
+async fn run_batch_job(client: &HttpClient, job: BatchJob, max_attempts: u32) -> Result<BatchJobReport> {
+    if let Some(expected_size) = job.expected_size {
+        if let Ok(metadata) = tokio::fs::metadata(&job.path).await {
+            if metadata.len() == expected_size {
+                debug!(path = ?job.path, size = expected_size, "Skipping job, target already at expected size");
+                return Ok(BatchJobReport {
+                    url: job.url,
+                    path: job.path,
+                    bytes: expected_size,
+                    size: format_file_size(expected_size),
+                    skipped: true,
+                });
+            }
+        }
+    }
+
+    let mut attempt = 0;
+    loop {
+        match download(client, &job.url, &job.path, None::<fn(u64, Option<u64>)>).await {
+            Ok(()) => {
+                let bytes = tokio::fs::metadata(&job.path).await.map(|m| m.len()).unwrap_or(0);
+                return Ok(BatchJobReport {
+                    url: job.url,
+                    path: job.path,
+                    bytes,
+                    size: format_file_size(bytes),
+                    skipped: false,
+                });
+            }
+            Err(e) if is_transient_download_error(&e) && attempt + 1 < max_attempts => {
+                attempt += 1;
+                let wait = StdDuration::from_millis(500 * 2u64.pow(attempt));
+                warn!(url = %job.url, attempt, error = %e, "Transient batch download failure, retrying after backoff");
+                tokio::time::sleep(wait).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Whether a batch job failure is worth retrying: timeouts, connection resets, and 5xx
+fn is_transient_download_error(error: &PixivError) -> bool {
+    match error {
+        PixivError::ApiError(msg) => {
+            msg.contains("500") || msg.contains("502") || msg.contains("503") || msg.contains("504")
+        }
+        PixivError::NetworkError(_) => true,
+        _ => false,
+    }
+}
+
+/// Zero-pad `page` to at least `width` digits, e.g. `pad_page(3, 3) == "003"`
+pub fn pad_page(page: u32, width: usize) -> String {
+    format!("{:0width$}", page, width = width)
+}
+
+/// Join a client's base URL with an API path, tolerating a trailing slash on
+/// `base` and/or a path prefix (e.g. behind a reverse proxy or API gateway)
+///
+/// `path` must start with `/`; exactly one `/` ends up between the two, so
+/// `join_base_url("https://proxy.example.com/pixiv/", "/v1/illust/detail")`
+/// and `join_base_url("https://proxy.example.com/pixiv", "/v1/illust/detail")`
+/// both yield `https://proxy.example.com/pixiv/v1/illust/detail`.
+pub fn join_base_url(base: &str, path: &str) -> String {
+    format!("{}/{}", base.trim_end_matches('/'), path.trim_start_matches('/'))
+}
+
+/// Truncate `title` to at most `max_chars` characters
+///
+/// Keeps a rendered [`FilenameTemplate`] segment from blowing past OS
+/// filename length limits (typically 255 bytes) when a work's title is
+/// unusually long.
+pub fn truncate_title(title: &str, max_chars: usize) -> String {
+    title.chars().take(max_chars).collect()
+}
+
+/// A compiled output filename pattern with `{name}` placeholders, e.g.
+/// `{user_account}/{illust_id}_p{page}_{title}.{ext}`
+///
+/// [`Self::render`] fills placeholders in from a context map, running each
+/// substituted value through [`safe_filename`] so the result stays
+/// filesystem-safe; literal path separators in the pattern itself are left
+/// untouched. An `{ext}` placeholder missing from the context falls back to
+/// the URL-derived extension ([`extract_extension`]) passed to `render`.
+#[derive(Debug, Clone)]
+pub struct FilenameTemplate {
+    pattern: String,
+}
+
+impl FilenameTemplate {
+    /// Compile a pattern string
+    pub fn new(pattern: impl Into<String>) -> Self {
+        Self { pattern: pattern.into() }
+    }
+
+    /// Render this template against `context`, falling back to `url` (via
+    /// [`extract_extension`]) for an unset `{ext}` placeholder
+    pub fn render(&self, context: &HashMap<String, String>, url: Option<&str>) -> String {
+        let mut rendered = String::with_capacity(self.pattern.len());
+        let mut rest = self.pattern.as_str();
+
+        while let Some(start) = rest.find('{') {
+            rendered.push_str(&rest[..start]);
+            let after_open = &rest[start + 1..];
+
+            let Some(end) = after_open.find('}') else {
+                rendered.push_str(&rest[start..]);
+                rest = "";
+                break;
+            };
+
+            let name = &after_open[..end];
+            let value = context
+                .get(name)
+                .cloned()
+                .or_else(|| if name == "ext" { url.and_then(extract_extension) } else { None })
+                .unwrap_or_default();
+            rendered.push_str(&safe_filename(&value));
+            rest = &after_open[end + 1..];
+        }
+
+        rendered.push_str(rest);
+        rendered
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -289,4 +637,85 @@ mod tests {
         assert_eq!(extract_extension("https://example.com/image"), None);
         assert_eq!(extract_extension("https://example.com/image.JPEG"), Some("jpeg".to_string()));
     }
+
+    #[test]
+    fn test_pad_page() {
+        assert_eq!(pad_page(3, 3), "003");
+        assert_eq!(pad_page(42, 2), "42");
+        assert_eq!(pad_page(1234, 2), "1234");
+    }
+
+    #[test]
+    fn test_join_base_url_trailing_slash_on_base() {
+        assert_eq!(
+            join_base_url("https://app-api.pixiv.net/", "/v1/illust/detail"),
+            "https://app-api.pixiv.net/v1/illust/detail"
+        );
+    }
+
+    #[test]
+    fn test_join_base_url_path_prefixed_base() {
+        assert_eq!(
+            join_base_url("https://proxy.example.com/pixiv", "/v1/illust/detail"),
+            "https://proxy.example.com/pixiv/v1/illust/detail"
+        );
+        assert_eq!(
+            join_base_url("https://proxy.example.com/pixiv/", "/v1/illust/detail"),
+            "https://proxy.example.com/pixiv/v1/illust/detail"
+        );
+    }
+
+    #[test]
+    fn test_truncate_title() {
+        assert_eq!(truncate_title("short", 10), "short");
+        assert_eq!(truncate_title("a very long title", 7), "a very ");
+    }
+
+    #[test]
+    fn test_filename_template_renders_placeholders() {
+        let template = FilenameTemplate::new("{user_account}/{illust_id}_p{page}_{title}.{ext}");
+        let mut context = HashMap::new();
+        context.insert("user_account".to_string(), "artist".to_string());
+        context.insert("illust_id".to_string(), "123".to_string());
+        context.insert("page".to_string(), pad_page(0, 3));
+        context.insert("title".to_string(), "My Work".to_string());
+
+        let rendered = template.render(&context, Some("https://example.com/a.png"));
+        assert_eq!(rendered, "artist/123_p000_My Work.png");
+    }
+
+    #[test]
+    fn test_filename_template_sanitizes_substituted_values() {
+        let template = FilenameTemplate::new("{title}.{ext}");
+        let mut context = HashMap::new();
+        context.insert("title".to_string(), "weird/name:here".to_string());
+
+        let rendered = template.render(&context, Some("https://example.com/a.jpg"));
+        assert_eq!(rendered, "weird_name_here.jpg");
+    }
+
+    #[test]
+    fn test_filename_template_missing_placeholder_renders_empty() {
+        let template = FilenameTemplate::new("{title}.{ext}");
+        let context = HashMap::new();
+
+        let rendered = template.render(&context, None);
+        assert_eq!(rendered, ".");
+    }
+
+    #[tokio::test]
+    async fn test_batch_job_with_expected_size_skips_matching_file() {
+        let mut path = std::env::temp_dir();
+        path.push(format!("pixiv_rs_batch_downloader_test_{}.bin", std::process::id()));
+        tokio::fs::write(&path, b"hello").await.unwrap();
+
+        let client = HttpClient::new().unwrap();
+        let job = BatchJob::new("https://example.com/unused", &path).with_expected_size(5);
+        let report = run_batch_job(&client, job, 1).await.unwrap();
+
+        assert!(report.skipped);
+        assert_eq!(report.bytes, 5);
+
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
 }
\ No newline at end of file
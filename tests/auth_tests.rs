@@ -1,5 +1,7 @@
 use pixiv_rs::auth::AuthClient;
 use pixiv_rs::error::PixivError;
+use pixiv_rs::{MemoryTokenStore, TokenStore};
+use std::sync::Arc;
 
 #[test]
 fn test_auth_client_creation() {
@@ -75,6 +77,49 @@ async fn test_login_with_invalid_credentials() {
     }
 }
 
+#[tokio::test]
+async fn test_restore_from_store_when_empty() {
+    let store: Arc<dyn TokenStore> = Arc::new(MemoryTokenStore::new());
+    let mut auth_client = AuthClient::new().unwrap().with_token_store(store);
+
+    let restored = auth_client.restore_from_store().await.unwrap();
+    assert!(restored.is_none());
+}
+
+#[tokio::test]
+async fn test_proactive_reauth_detects_expired_token() {
+    use pixiv_rs::auth::{AuthResponse, ProfileImageUrls, User};
+    use pixiv_rs::ReauthHandler;
+    use std::time::Duration;
+
+    let store: Arc<dyn TokenStore> = Arc::new(MemoryTokenStore::new());
+    let expired = AuthResponse {
+        access_token: "old_token".to_string(),
+        refresh_token: "refresh_token".to_string(),
+        token_type: "Bearer".to_string(),
+        expires_in: 3600,
+        user: User {
+            id: 1,
+            name: "test".to_string(),
+            account: "test".to_string(),
+            email: None,
+            profile_image_urls: ProfileImageUrls {
+                px_16x16: None,
+                px_50x50: None,
+                px_170x170: None,
+            },
+        },
+        obtained_at: chrono::Utc::now() - chrono::Duration::hours(2),
+    };
+    store.save(&expired).await.unwrap();
+
+    let mut auth_client = AuthClient::new().unwrap().with_token_store(store);
+    auth_client.restore_from_store().await.unwrap();
+
+    // Token expired an hour ago, so a 60s skew window should trigger a refresh
+    assert!(auth_client.should_refresh(Duration::from_secs(60)));
+}
+
 #[tokio::test]
 async fn test_refresh_with_invalid_token() {
     let mut auth_client = AuthClient::new().unwrap();
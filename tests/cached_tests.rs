@@ -0,0 +1,102 @@
+#![cfg(feature = "sqlite-cache")]
+
+use pixiv_rs::{AppClient, CachedAppClient, HttpClient};
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+/// A `/v2/novel/detail` response, wrapped in the `{"novel": {...}}` envelope
+/// the real Pixiv API uses
+const NOVEL_DETAIL_ENVELOPE: &str = r#"{"novel":{"id":1,"title":"Test Novel","type":"novel","caption":"A test novel","restrict":0,"user":{"id":2,"name":"Author","account":"author_account","profile_image_urls":{"medium":"https://example.com/avatar.png"},"comment":null,"is_followed":null},"tags":[],"create_date":"2024-01-01T00:00:00+09:00","page_count":1,"text_length":100,"series":null,"total_view":0,"total_bookmarks":0,"is_bookmarked":false,"visible":true,"is_muted":false,"is_mypixiv_only":false,"is_x_restricted":false,"novel_ai_type":0,"comment_access_control":null}}"#;
+
+/// Spawn a one-shot local HTTP server that replies to a single request with `body`
+fn spawn_mock_server(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    std::thread::spawn(move || {
+        if let Ok((mut stream, _)) = listener.accept() {
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+
+    format!("http://{}", addr)
+}
+
+/// Like [`spawn_mock_server`], but also hands back the request's start-line
+/// (e.g. `GET /pixiv/v2/novel/detail?novel_id=1 HTTP/1.1`) so a test can
+/// assert on the path actually requested
+fn spawn_mock_server_capturing(body: &'static str) -> (String, Arc<Mutex<Option<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let captured = Arc::new(Mutex::new(None));
+    let captured_clone = captured.clone();
+
+    std::thread::spawn(move || {
+        if let Ok((mut stream, _)) = listener.accept() {
+            let mut buf = [0u8; 1024];
+            if let Ok(n) = stream.read(&mut buf) {
+                let request_line = String::from_utf8_lossy(&buf[..n]).lines().next().unwrap_or("").to_string();
+                *captured_clone.lock().unwrap() = Some(request_line);
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+
+    (format!("http://{}", addr), captured)
+}
+
+#[tokio::test]
+async fn test_cached_app_client_novel_detail_unwraps_envelope() {
+    let base_url = spawn_mock_server(NOVEL_DETAIL_ENVELOPE);
+
+    let mut app_client = AppClient::new(HttpClient::new().unwrap());
+    app_client.set_base_url(base_url);
+
+    let mut db_path = std::env::temp_dir();
+    db_path.push(format!("pixiv_rs_cached_app_client_novel_detail_test_{}.sqlite", std::process::id()));
+    let _ = std::fs::remove_file(&db_path);
+
+    let cached_client = CachedAppClient::open(app_client, &db_path).unwrap();
+    let novel = cached_client.novel_detail(1).await.unwrap();
+
+    assert_eq!(novel.id, 1);
+    assert_eq!(novel.title, "Test Novel");
+
+    std::fs::remove_file(&db_path).ok();
+}
+
+#[tokio::test]
+async fn test_cached_app_client_respects_prefixed_trailing_slash_base_url() {
+    let (base_url, captured) = spawn_mock_server_capturing(NOVEL_DETAIL_ENVELOPE);
+
+    let mut app_client = AppClient::new(HttpClient::new().unwrap());
+    app_client.set_base_url(format!("{}/pixiv/", base_url));
+
+    let mut db_path = std::env::temp_dir();
+    db_path.push(format!("pixiv_rs_cached_app_client_prefixed_base_url_test_{}.sqlite", std::process::id()));
+    let _ = std::fs::remove_file(&db_path);
+
+    let cached_client = CachedAppClient::open(app_client, &db_path).unwrap();
+    cached_client.novel_detail(1).await.unwrap();
+
+    let request_line = captured.lock().unwrap().clone().unwrap_or_default();
+    assert!(
+        request_line.contains("/pixiv/v2/novel/detail"),
+        "expected proxy path prefix to be preserved, request line was: {request_line}"
+    );
+
+    std::fs::remove_file(&db_path).ok();
+}
@@ -67,6 +67,6 @@ async fn test_download_function_exists() {
     
     // Since we don't have a real image URL, here we only verify the function signature
     // Actual usage needs to provide a valid URL
-    // let result = download(&client, "https://example.com/image.jpg", path).await;
+    // let result = download(&client, "https://example.com/image.jpg", path, None::<fn(u64, Option<u64>)>).await;
     // assert!(result.is_ok() || result.is_err()); // Only verify that the function can be called
 }
\ No newline at end of file